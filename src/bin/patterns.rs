@@ -0,0 +1,60 @@
+//! generate a pattern database for the solver, for example:
+//! `patterns 4x4 6-6-3 assets/patterns/4x4.pdb`
+//! groups are sizes of blocks of numbers, or numbers like `1,2,5/3,4,6`
+use sliding_puzzle_game::solver::PatternDatabase;
+use std::{env, fs, process};
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let (size, groups, output) = match &args[..] {
+        [size, groups, output] => (size, groups, output),
+        _ => exit("usage: patterns <width>x<height> <groups> <output>"),
+    };
+    let (width, height) = size
+        .split_once('x')
+        .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+        .filter(|&(width, height): &(usize, usize)| width > 1 && height > 1)
+        .unwrap_or_else(|| exit("the size should be like 4x4"));
+    let groups = parse_groups(groups, width, height)
+        .unwrap_or_else(|| exit("the groups should be like 6-6-3 or 1,2,5/3,4,6"));
+    groups
+        .iter()
+        .for_each(|group| println!("group {:?}", group));
+    let database = PatternDatabase::generate(width, height, &groups);
+    fs::write(output, database.to_bytes()).unwrap_or_else(|err| exit(&err.to_string()));
+    println!("saved to {}", output);
+}
+
+fn parse_groups(groups: &str, width: usize, height: usize) -> Option<Vec<Vec<u8>>> {
+    let cells = width * height;
+    let groups = if groups.contains(',') {
+        groups
+            .split('/')
+            .map(|group| group.split(',').map(|tile| tile.parse().ok()).collect())
+            .collect::<Option<Vec<Vec<u8>>>>()?
+    } else {
+        let sizes = groups
+            .split('-')
+            .map(|size| size.parse().ok())
+            .collect::<Option<Vec<usize>>>()?;
+        (sizes.iter().sum::<usize>() < cells)
+            .then(|| PatternDatabase::partition(width, height, &sizes))?
+    };
+    // numbers on board, each in one group only
+    let mut tiles = groups.iter().flatten().copied().collect::<Vec<_>>();
+    let len = tiles.len();
+    tiles.sort_unstable();
+    tiles.dedup();
+    let valid = tiles.len() == len
+        && tiles
+            .iter()
+            .all(|&tile| tile > 0 && (tile as usize) < cells)
+        && groups.len() <= 5
+        && groups.iter().all(|group| (1..=8).contains(&group.len()));
+    valid.then_some(groups)
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2)
+}
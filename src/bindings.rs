@@ -0,0 +1,249 @@
+//! the keys of each action, rebound in the bindings screen and kept
+//! between sessions.
+use crate::{puzzle::Move, storage};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeyBindings {
+    // two keys at most for each action
+    pub keys: BTreeMap<Action, [Option<Shortcut>; 2]>,
+    // move the blank by arrows instead of the number
+    pub move_blank: bool,
+}
+
+impl KeyBindings {
+    const KEY: &'static str = "bindings.ron";
+
+    // the saved bindings, missing actions are bound by default
+    pub fn load() -> Self {
+        storage::load::<Self>(Self::KEY)
+            .map(Self::with_defaults)
+            .unwrap_or_default()
+    }
+
+    // bind missing actions by default, except keys put on others
+    fn with_defaults(mut self) -> Self {
+        Self::default().keys.into_iter().for_each(|(action, keys)| {
+            if !self.keys.contains_key(&action) {
+                let keys = keys.map(|key| key.filter(|key| !self.is_bound(key)));
+                self.keys.insert(action, keys);
+            }
+        });
+        self
+    }
+
+    fn is_bound(&self, key: &Shortcut) -> bool {
+        self.keys
+            .values()
+            .flatten()
+            .flatten()
+            .any(|bound| bound == key)
+    }
+
+    pub fn save(&self) {
+        storage::save(Self::KEY, self);
+    }
+
+    // actions of the keys just pressed
+    pub fn just_pressed<'a>(
+        &'a self,
+        keyboard: &'a Input<KeyCode>,
+    ) -> impl Iterator<Item = Action> + 'a {
+        self.keys
+            .iter()
+            .filter(|(_, keys)| keys.iter().flatten().any(|key| key.just_pressed(keyboard)))
+            .map(|(&action, _)| action)
+    }
+
+    // whether a key of the action is held
+    pub fn pressed(&self, keyboard: &Input<KeyCode>, action: Action) -> bool {
+        self.keys
+            .get(&action)
+            .is_some_and(|keys| keys.iter().flatten().any(|key| key.pressed(keyboard)))
+    }
+
+    // the move of a number when an arrow is pressed
+    pub fn direction(&self, mv: Move) -> Move {
+        match self.move_blank {
+            true => mv.opposite(),
+            false => mv,
+        }
+    }
+
+    // bind the key to one action only
+    pub fn bind(&mut self, action: Action, slot: usize, key: Option<Shortcut>) {
+        if key.is_some() {
+            self.keys.values_mut().flatten().for_each(|bound| {
+                if *bound == key {
+                    *bound = None;
+                }
+            });
+        }
+        self.keys.entry(action).or_default()[slot] = key;
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        use KeyCode::*;
+        Self {
+            keys: [
+                (
+                    Action::MoveUp,
+                    [Some(Shortcut::new(Up)), Some(Shortcut::new(W))],
+                ),
+                (
+                    Action::MoveDown,
+                    [Some(Shortcut::new(Down)), Some(Shortcut::new(S))],
+                ),
+                (
+                    Action::MoveLeft,
+                    [Some(Shortcut::new(Left)), Some(Shortcut::new(A))],
+                ),
+                (
+                    Action::MoveRight,
+                    [Some(Shortcut::new(Right)), Some(Shortcut::new(D))],
+                ),
+                (
+                    Action::Undo,
+                    [Some(Shortcut::control(Z)), Some(Shortcut::new(Back))],
+                ),
+                (Action::Redo, [Some(Shortcut::control(Y)), None]),
+                (Action::Reset, [Some(Shortcut::new(R)), None]),
+                (Action::Back, [Some(Shortcut::new(M)), None]),
+                (Action::Hint, [Some(Shortcut::new(H)), None]),
+                (Action::Numbers, [Some(Shortcut::new(N)), None]),
+                (Action::Peek, [Some(Shortcut::new(P)), None]),
+                (Action::Copy, [Some(Shortcut::control(C)), None]),
+                (Action::Paste, [Some(Shortcut::control(V)), None]),
+            ]
+            .into_iter()
+            .collect(),
+            move_blank: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Undo,
+    Redo,
+    Reset,
+    Back,
+    Hint,
+    Numbers,
+    Peek,
+    Copy,
+    Paste,
+}
+
+impl Action {
+    pub const ALL: [Self; 13] = [
+        Self::MoveUp,
+        Self::MoveDown,
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::Undo,
+        Self::Redo,
+        Self::Reset,
+        Self::Back,
+        Self::Hint,
+        Self::Numbers,
+        Self::Peek,
+        Self::Copy,
+        Self::Paste,
+    ];
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::MoveUp => "Move up",
+            Self::MoveDown => "Move down",
+            Self::MoveLeft => "Move left",
+            Self::MoveRight => "Move right",
+            Self::Undo => "Undo",
+            Self::Redo => "Redo",
+            Self::Reset => "Reset",
+            Self::Back => "Back",
+            Self::Hint => "Hint",
+            Self::Numbers => "Show numbers",
+            Self::Peek => "Peek picture",
+            Self::Copy => "Copy position",
+            Self::Paste => "Paste position",
+        })
+    }
+}
+
+// a key, with control held or not
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Shortcut {
+    pub key: KeyCode,
+    pub control: bool,
+}
+
+impl Shortcut {
+    pub const fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            control: false,
+        }
+    }
+
+    pub const fn control(key: KeyCode) -> Self {
+        Self { key, control: true }
+    }
+
+    pub fn just_pressed(&self, keyboard: &Input<KeyCode>) -> bool {
+        keyboard.just_pressed(self.key) && is_control_pressed(keyboard) == self.control
+    }
+
+    pub fn pressed(&self, keyboard: &Input<KeyCode>) -> bool {
+        keyboard.pressed(self.key) && is_control_pressed(keyboard) == self.control
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.control {
+            true => write!(f, "Ctrl+{:?}", self.key),
+            false => write!(f, "{:?}", self.key),
+        }
+    }
+}
+
+pub fn is_control_pressed(keyboard: &Input<KeyCode>) -> bool {
+    keyboard.any_pressed([KeyCode::LControl, KeyCode::RControl])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_actions_keep_keys_of_others() {
+        let mut saved = KeyBindings::default();
+        saved.keys.remove(&Action::Hint);
+        saved.keys.remove(&Action::Reset);
+        // H is put on peek before hint is known
+        saved
+            .keys
+            .insert(Action::Peek, [Some(Shortcut::new(KeyCode::H)), None]);
+        let bindings = saved.with_defaults();
+        assert_eq!(bindings.keys[&Action::Hint], [None, None]);
+        assert_eq!(
+            bindings.keys[&Action::Reset],
+            [Some(Shortcut::new(KeyCode::R)), None]
+        );
+        assert_eq!(
+            bindings.keys[&Action::Peek],
+            [Some(Shortcut::new(KeyCode::H)), None]
+        );
+    }
+}
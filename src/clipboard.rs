@@ -0,0 +1,70 @@
+//! copy and paste text, through the clipboard of system on desktop, or
+//! prompts of the browser on wasm where the clipboard is not reachable.
+
+// put the text in clipboard
+pub fn copy(text: &str) -> Result<(), String> {
+    imp::copy(text)
+}
+
+// the text in clipboard, none if the player cancelled
+pub fn paste() -> Result<Option<String>, String> {
+    imp::paste()
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+mod imp {
+    use arboard::Clipboard;
+    use std::sync::Mutex;
+
+    // the text copied is gone with the clipboard on some systems, so it
+    // is kept as long as the app runs
+    static CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+
+    fn with<T>(f: impl FnOnce(&mut Clipboard) -> Result<T, arboard::Error>) -> Result<T, String> {
+        let mut clipboard = CLIPBOARD.lock().map_err(|err| err.to_string())?;
+        if clipboard.is_none() {
+            *clipboard = Some(Clipboard::new().map_err(|err| err.to_string())?);
+        }
+        f(clipboard.as_mut().unwrap()).map_err(|err| err.to_string())
+    }
+
+    pub fn copy(text: &str) -> Result<(), String> {
+        with(|clipboard| clipboard.set_text(text))
+    }
+
+    pub fn paste() -> Result<Option<String>, String> {
+        with(|clipboard| clipboard.get_text()).map(Some)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod imp {
+    fn window() -> Result<web_sys::Window, String> {
+        web_sys::window().ok_or_else(|| "no window".to_string())
+    }
+
+    // the player copies the text selected in prompt
+    pub fn copy(text: &str) -> Result<(), String> {
+        window()?
+            .prompt_with_message_and_default("Copy the text below", text)
+            .map(|_| ())
+            .map_err(|err| format!("{:?}", err))
+    }
+
+    pub fn paste() -> Result<Option<String>, String> {
+        window()?
+            .prompt_with_message("Paste the text below")
+            .map_err(|err| format!("{:?}", err))
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+mod imp {
+    pub fn copy(_: &str) -> Result<(), String> {
+        Err("no clipboard on this system".to_string())
+    }
+
+    pub fn paste() -> Result<Option<String>, String> {
+        Err("no clipboard on this system".to_string())
+    }
+}
@@ -1,9 +1,13 @@
+use crate::{
+    theme::{Theme, Themed},
+    TextLabel, TextScale,
+};
 use bevy::prelude::*;
 
 pub struct Floating;
 
 impl Floating {
-    fn setup(mut commands: Commands, server: Res<AssetServer>) {
+    fn setup(mut commands: Commands, theme: Res<Theme>) {
         commands
             .spawn_bundle(NodeBundle {
                 style: Style {
@@ -39,53 +43,139 @@ impl Floating {
                             })
                             .insert(label)
                             .with_children(|parent| {
-                                parent.spawn_bundle(TextBundle {
-                                    text: Text::with_section(
-                                        text,
-                                        TextStyle {
-                                            color: Color::BLACK,
-                                            font: server.load("fonts/VictorMono-BoldItalic.ttf"),
-                                            font_size: 32.0,
-                                        },
-                                        Default::default(),
-                                    ),
-                                    ..Default::default()
-                                });
+                                parent
+                                    .spawn_bundle(TextBundle {
+                                        text: Text::with_section(
+                                            text,
+                                            TextStyle {
+                                                font_size: 32.0,
+                                                ..theme.style(Themed::Icon)
+                                            },
+                                            Default::default(),
+                                        ),
+                                        ..Default::default()
+                                    })
+                                    .insert(Themed::Icon);
                             });
                     });
             });
     }
 
-    fn info_system() {
-        // pop-up box
+    fn info_system(commands: &mut Commands, theme: &Theme) -> Entity {
+        Self::popup(
+            commands,
+            theme,
+            concat!(
+                "Sliding Puzzle Game v",
+                env!("CARGO_PKG_VERSION"),
+                "\nby ",
+                env!("CARGO_PKG_AUTHORS"),
+                "\n",
+                env!("CARGO_PKG_LICENSE"),
+                " License",
+            ),
+        )
     }
 
-    fn help_system() {
-        // pop-up box
+    fn help_system(commands: &mut Commands, theme: &Theme) -> Entity {
+        Self::popup(
+            commands,
+            theme,
+            "Move the sliders back in order.\n\
+            Click or drag a slider in line with\n\
+            the blank, or use Arrow/WASD keys.\n\
+            Keys can be changed in the settings.\n\
+            Reset restores the scramble.",
+        )
     }
 
+    // pop-up box in the middle of window
+    fn popup(commands: &mut Commands, theme: &Theme, text: &str) -> Entity {
+        commands
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    position: Rect {
+                        left: Val::Percent(20.0),
+                        bottom: Val::Percent(30.0),
+                        ..Default::default()
+                    },
+                    position_type: PositionType::Absolute,
+                    size: Size::new(Val::Percent(60.0), Val::Percent(40.0)),
+                    ..Default::default()
+                },
+                color: theme.palette.popup.into(),
+                ..Default::default()
+            })
+            .insert(Themed::Popup)
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(TextBundle {
+                        text: Text::with_section(
+                            text,
+                            theme.style(Themed::Text),
+                            TextAlignment {
+                                horizontal: HorizontalAlign::Center,
+                                vertical: VerticalAlign::Center,
+                            },
+                        ),
+                        ..Default::default()
+                    })
+                    .insert(TextLabel::with_section(TextScale::new(0.025, 0.05)))
+                    .insert(Themed::Text);
+            })
+            .id()
+    }
+
+    #[allow(clippy::type_complexity)]
     fn update(
+        mut commands: Commands,
+        mut popup: ResMut<Popup>,
         server: Res<AssetServer>,
+        theme: Res<Theme>,
         mut query: Query<(&Interaction, &Label, &mut UiImage), (Changed<Interaction>, With<Label>)>,
     ) {
         query.for_each_mut(|(interaction, label, mut image)| match interaction {
             Interaction::None => *image = server.load("images/button_blue.png").into(),
             Interaction::Hovered => *image = server.load("images/button_blue_hovered.png").into(),
-            Interaction::Clicked => match label {
-                Label::Help => Self::help_system(),
-                Label::Info => Self::info_system(),
-            },
+            Interaction::Clicked => {
+                // close the opened one, open another if it is different
+                let opened = popup.0.take().map(|(opened, entity)| {
+                    commands.entity(entity).despawn_recursive();
+                    opened
+                });
+                if opened != Some(*label) {
+                    let entity = match label {
+                        Label::Help => Self::help_system(&mut commands, &theme),
+                        Label::Info => Self::info_system(&mut commands, &theme),
+                    };
+                    popup.0 = Some((*label, entity));
+                }
+            }
         });
     }
 }
 
 impl Plugin for Floating {
     fn build(&self, app: &mut App) {
-        app.add_startup_system(Self::setup).add_system(Self::update);
+        app.init_resource::<Popup>()
+            .add_startup_system(Self::setup)
+            .add_system(Self::update);
+    }
+}
+
+// the opened pop-up box
+#[derive(Default)]
+pub struct Popup(Option<(Label, Entity)>);
+
+impl Popup {
+    pub fn is_open(&self) -> bool {
+        self.0.is_some()
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
 enum Label {
     Help,
     Info,
@@ -0,0 +1,147 @@
+//! select and press buttons without a mouse, by the tab key or gamepads,
+//! the focused button is highlighted as if it is hovered.
+use super::GameState;
+use crate::{
+    bindings::KeyBindings,
+    theme::{Theme, Themed},
+};
+use bevy::{prelude::*, ui::UiSystem};
+
+pub struct Focus;
+
+impl Focus {
+    #[allow(clippy::too_many_arguments)]
+    fn navigate(
+        keyboard: Res<Input<KeyCode>>,
+        bindings: Res<KeyBindings>,
+        gamepads: Res<Gamepads>,
+        buttons: Res<Input<GamepadButton>>,
+        state: Res<State<GameState>>,
+        mut focused: ResMut<Focused>,
+        mut pressed: Local<Option<Entity>>,
+        mut query: Query<(Entity, &Node, &GlobalTransform, &mut Interaction), With<Button>>,
+    ) {
+        // a pressed button is released the next frame
+        if let Some(entity) = pressed.take() {
+            if let Ok((.., mut interaction)) = query.get_mut(entity) {
+                if *interaction == Interaction::Clicked {
+                    *interaction = Interaction::None;
+                }
+            }
+        }
+        let shift = keyboard.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+        let tab = keyboard.just_pressed(KeyCode::Tab);
+        // the keys press the button unless they are bound to an action
+        let press = keyboard.any_just_pressed([KeyCode::Return, KeyCode::Space])
+            && bindings.just_pressed(&keyboard).next().is_none();
+        let keys = [
+            (tab && shift, Navigation::Previous),
+            (tab && !shift, Navigation::Next),
+            (press, Navigation::Press),
+        ];
+        // the d-pad moves sliders in game
+        let in_game = state.current() == &GameState::Game;
+        let buttons = &*buttons;
+        let pads = gamepads.iter().flat_map(|&gamepad| {
+            [
+                (GamepadButtonType::LeftTrigger, Navigation::Previous, true),
+                (GamepadButtonType::RightTrigger, Navigation::Next, true),
+                (GamepadButtonType::South, Navigation::Press, true),
+                (GamepadButtonType::DPadUp, Navigation::Previous, !in_game),
+                (GamepadButtonType::DPadLeft, Navigation::Previous, !in_game),
+                (GamepadButtonType::DPadDown, Navigation::Next, !in_game),
+                (GamepadButtonType::DPadRight, Navigation::Next, !in_game),
+            ]
+            .into_iter()
+            .map(move |(button, navigation, enabled)| {
+                let pressed = buttons.just_pressed(GamepadButton(gamepad, button));
+                (enabled && pressed, navigation)
+            })
+        });
+        let navigation = match keys
+            .into_iter()
+            .chain(pads)
+            .find_map(|(pressed, navigation)| pressed.then_some(navigation))
+        {
+            Some(navigation) => navigation,
+            None => return,
+        };
+        // visible buttons from top to bottom and left to right
+        let mut visible = query
+            .iter()
+            .filter(|(_, node, ..)| node.size != Vec2::ZERO)
+            .map(|(entity, _, transform, _)| {
+                let (x, y) = (transform.translation.x, transform.translation.y);
+                ((-y.round() as i32, x.round() as i32), entity)
+            })
+            .collect::<Vec<_>>();
+        visible.sort_unstable();
+        let len = visible.len();
+        let index = focused
+            .0
+            .and_then(|focused| visible.iter().position(|&(_, entity)| entity == focused));
+        match (navigation, index) {
+            (_, _) if len == 0 => focused.0 = None,
+            (Navigation::Previous, Some(index)) => {
+                focused.0 = Some(visible[(index + len - 1) % len].1)
+            }
+            (Navigation::Next, Some(index)) => focused.0 = Some(visible[(index + 1) % len].1),
+            (Navigation::Press, Some(index)) => {
+                let entity = visible[index].1;
+                if let Ok((.., mut interaction)) = query.get_mut(entity) {
+                    *interaction = Interaction::Clicked;
+                    *pressed = Some(entity);
+                }
+            }
+            // focus the first one at the beginning
+            (_, None) => focused.0 = Some(visible[0].1),
+        }
+    }
+
+    // after the buttons are colored by their screens, the images of
+    // floating buttons are tinted and back to white once unfocused
+    #[allow(clippy::type_complexity)]
+    fn highlight(
+        focused: Res<Focused>,
+        theme: Res<Theme>,
+        mut last: Local<Option<Entity>>,
+        mut query: Query<(&Interaction, &mut UiColor, Option<&Themed>), With<Button>>,
+    ) {
+        if *last != focused.0 {
+            if let Some(Ok((interaction, mut color, themed))) =
+                last.map(|entity| query.get_mut(entity))
+            {
+                match (interaction, themed) {
+                    // hovered images are swapped, not tinted
+                    (_, None) => *color = Color::WHITE.into(),
+                    (Interaction::None, Some(_)) => *color = theme.palette.button.into(),
+                    _ => (),
+                }
+            }
+            *last = focused.0;
+        }
+        if let Some(Ok((Interaction::None, mut color, _))) =
+            focused.0.map(|entity| query.get_mut(entity))
+        {
+            *color = theme.palette.hovered.into();
+        }
+    }
+}
+
+impl Plugin for Focus {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Focused>()
+            .add_system_to_stage(CoreStage::PreUpdate, Self::navigate.after(UiSystem::Focus))
+            .add_system_to_stage(CoreStage::PostUpdate, Self::highlight);
+    }
+}
+
+// the focused button, if any
+#[derive(Default)]
+pub struct Focused(Option<Entity>);
+
+enum Navigation {
+    Previous,
+    Next,
+    Press,
+}
@@ -1,8 +1,18 @@
-use super::{Game, GameState};
-use crate::{TextLabel, TextScale};
+use super::{
+    counter,
+    hint::ShouldHint,
+    history::ShouldRewind,
+    playback::{Playback, ShouldPlay},
+    share::ShouldShare,
+    status, timer, Game, GameState,
+};
+use crate::{
+    theme::{Theme, Themed},
+    TextLabel, TextScale,
+};
 use bevy::prelude::*;
 
-pub fn setup(mut commands: Commands, server: Res<AssetServer>) {
+pub fn setup(mut commands: Commands, theme: Res<Theme>) {
     commands
         .spawn_bundle(NodeBundle {
             style: Style {
@@ -22,73 +32,173 @@ pub fn setup(mut commands: Commands, server: Res<AssetServer>) {
         // as a part of game
         .insert(Game)
         .with_children(|parent| {
-            [(Label::Reset, "Reset"), (Label::Back, "Back")]
-                .into_iter()
-                .for_each(|(label, text)| {
-                    parent // space item
-                        .spawn_bundle(NodeBundle {
-                            style: Style {
-                                size: Size {
-                                    height: Val::Px(16.0),
-                                    ..Default::default()
-                                },
-                                ..Default::default()
-                            },
-                            visibility: Visibility { is_visible: false },
-                            ..Default::default()
-                        });
-                    parent // button
-                        .spawn_bundle(ButtonBundle {
-                            style: Style {
-                                align_items: AlignItems::Center,
-                                justify_content: JustifyContent::Center,
-                                padding: Rect::all(Val::Percent(1.0)),
-                                ..Default::default()
-                            },
-                            ..Default::default()
-                        })
-                        .insert(label)
-                        .with_children(|parent| {
-                            parent
-                                .spawn_bundle(TextBundle {
-                                    text: Text::with_section(
-                                        text,
-                                        TextStyle {
-                                            color: Color::OLIVE,
-                                            font: server.load("fonts/VictorMono-BoldItalic.ttf"),
-                                            ..Default::default()
-                                        },
-                                        Default::default(),
-                                    ),
-                                    ..Default::default()
-                                })
-                                .insert(TextLabel::with_section(TextScale::new(0.04, 0.08)));
-                        });
+            counter::spawn(parent, &theme);
+            timer::spawn(parent, &theme);
+            [
+                (Label::Hint, "Hint"),
+                (Label::Solve, "Solve"),
+                (Label::Reset, "Reset"),
+                (Label::Back, "Back"),
+            ]
+            .into_iter()
+            .for_each(|(label, text)| {
+                space(parent);
+                button(parent, &theme, label, text);
+            });
+            space(parent);
+            parent
+                // rewind the board
+                .spawn_bundle(NodeBundle {
+                    visibility: Visibility { is_visible: false },
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    button(parent, &theme, Label::Undo, "Undo");
+                    button(parent, &theme, Label::Redo, "Redo");
+                });
+            space(parent);
+            parent
+                // share the board as text
+                .spawn_bundle(NodeBundle {
+                    visibility: Visibility { is_visible: false },
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    button(parent, &theme, Label::Copy, "Copy");
+                    button(parent, &theme, Label::Paste, "Paste");
                 });
+            status::spawn(parent, &theme);
+            space(parent);
+            parent
+                // playback controls, shown while solving
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        display: Display::None,
+                        ..Default::default()
+                    },
+                    visibility: Visibility { is_visible: false },
+                    ..Default::default()
+                })
+                .insert(PlaybackControls)
+                .with_children(|parent| {
+                    button(parent, &theme, Label::Pause, "Pause");
+                    button(parent, &theme, Label::Step, "Step");
+                    button(parent, &theme, Label::Stop, "Stop");
+                });
+        });
+}
+
+fn space(parent: &mut ChildBuilder) {
+    parent.spawn_bundle(NodeBundle {
+        style: Style {
+            size: Size {
+                height: Val::Px(16.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        visibility: Visibility { is_visible: false },
+        ..Default::default()
+    });
+}
+
+fn button(parent: &mut ChildBuilder, theme: &Theme, label: Label, text: &str) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                padding: Rect::all(Val::Percent(1.0)),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(label)
+        .insert(Themed::Button)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: theme.text(text, Themed::ButtonText),
+                    ..Default::default()
+                })
+                .insert(TextLabel::with_section(TextScale::new(0.04, 0.08)))
+                .insert(Themed::ButtonText)
+                .insert(label);
         });
 }
 
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn interaction(
     mut writer: EventWriter<ShouldBeRestored>,
+    mut hint: EventWriter<ShouldHint>,
+    mut play: EventWriter<ShouldPlay>,
+    mut rewind: EventWriter<ShouldRewind>,
+    mut share: EventWriter<ShouldShare>,
     mut state: ResMut<State<GameState>>,
+    theme: Res<Theme>,
     mut query: Query<(&Interaction, &Label, &mut UiColor), (Changed<Interaction>, With<Label>)>,
 ) {
     query.for_each_mut(|(interaction, label, mut color)| match interaction {
         Interaction::Clicked => match label {
+            Label::Hint => hint.send(ShouldHint),
+            Label::Solve => play.send(ShouldPlay::Solve),
+            Label::Pause => play.send(ShouldPlay::Pause),
+            Label::Step => play.send(ShouldPlay::Step),
+            Label::Stop => play.send(ShouldPlay::Stop),
+            Label::Undo => rewind.send(ShouldRewind::Undo),
+            Label::Redo => rewind.send(ShouldRewind::Redo),
+            Label::Copy => share.send(ShouldShare::Copy),
+            Label::Paste => share.send(ShouldShare::Paste),
             Label::Reset => writer.send(ShouldBeRestored),
-            Label::Back => state.set(GameState::Menu).unwrap(),
+            Label::Back => state.set(GameState::Menu).unwrap_or_default(),
         },
-        Interaction::Hovered => *color = Color::GOLD.into(),
-        Interaction::None => *color = Color::YELLOW.into(),
+        Interaction::Hovered => *color = theme.palette.hovered.into(),
+        Interaction::None => *color = theme.palette.button.into(),
     });
 }
 
+// show the controls only while solving
+pub fn playback(
+    playback: Res<Playback>,
+    mut controls: Query<&mut Style, With<PlaybackControls>>,
+    mut text: Query<(&Label, &mut Text), Without<Interaction>>,
+) {
+    if playback.is_changed() {
+        controls.for_each_mut(|mut style| {
+            style.display = match playback.is_active() {
+                true => Display::Flex,
+                false => Display::None,
+            };
+        });
+        text.for_each_mut(|(label, mut text)| {
+            if let Label::Pause = label {
+                text.sections[0].value = match playback.is_paused() {
+                    true => "Play".to_string(),
+                    false => "Pause".to_string(),
+                };
+            }
+        });
+    }
+}
+
 #[repr(transparent)]
 pub struct ShouldBeRestored;
 
-// buttons
 #[derive(Component)]
+pub struct PlaybackControls;
+
+// buttons, and the text of them
+#[derive(Component, Clone, Copy)]
 pub enum Label {
+    Hint,
+    Solve,
+    Pause,
+    Step,
+    Stop,
+    Undo,
+    Redo,
+    Copy,
+    Paste,
     Reset,
     Back,
 }
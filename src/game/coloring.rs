@@ -0,0 +1,117 @@
+//! tint the sliders by where they belong, the goal row, column or layer of
+//! the layer by layer solving, and show a faint slider at the goal of the
+//! hovered one, for beginners to see the structure of board.
+use super::{Board, Game};
+use crate::{
+    puzzle::Puzzle,
+    theme::{Theme, Themed},
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TileColoring {
+    #[default]
+    None,
+    Row,
+    Column,
+    // the top row or left column solved at a time
+    Fringe,
+}
+
+impl TileColoring {
+    pub const ALL: [Self; 4] = [Self::None, Self::Row, Self::Column, Self::Fringe];
+
+    pub fn next(self) -> Self {
+        crate::cycle(&Self::ALL, self)
+    }
+
+    // hues apart for each group, white when off or for blank
+    pub fn tint(self, tile: usize, puzzle: &Puzzle) -> Color {
+        let (width, height) = (puzzle.width(), puzzle.height());
+        let (x, y) = puzzle.goal(tile);
+        let (group, groups) = match self {
+            Self::None => return Color::WHITE,
+            _ if tile == 0 => return Color::WHITE,
+            Self::Row => (y, height),
+            Self::Column => (x, width),
+            Self::Fringe => (
+                fringe(x, y, width, height),
+                fringe(width - 1, height - 1, width, height) + 1,
+            ),
+        };
+        // not back to red at the last
+        Color::hsl(300.0 * group as f32 / (groups - 1).max(1) as f32, 0.8, 0.75)
+    }
+}
+
+impl fmt::Display for TileColoring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::None => "off",
+            Self::Row => "rows",
+            Self::Column => "columns",
+            Self::Fringe => "fringe",
+        })
+    }
+}
+
+// the layer of a cell, peeling the longer side of what is left, a row from
+// the top or a column from the left, until the last 2x2 is solved at once
+fn fringe(x: usize, y: usize, width: usize, height: usize) -> usize {
+    let (mut left, mut top, mut layer) = (0, 0, 0);
+    while width - left > 2 || height - top > 2 {
+        match height - top >= width - left {
+            true if y == top => return layer,
+            true => top += 1,
+            false if x == left => return layer,
+            false => left += 1,
+        }
+        layer += 1;
+    }
+    layer
+}
+
+#[derive(Component)]
+pub struct Ghost;
+
+pub fn spawn(commands: &mut Commands, theme: &Theme) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            texture: theme.slider.clone(),
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .insert(Ghost)
+        .insert(Themed::Slider)
+        .insert(Game);
+}
+
+// over the goal of the slider under mouse, while tinted
+pub fn ghost(
+    windows: Res<Windows>,
+    board: Res<Board>,
+    coloring: Res<TileColoring>,
+    mut query: Query<(&mut Sprite, &mut Transform, &mut Visibility), With<Ghost>>,
+) {
+    let hovered = windows
+        .get_primary()
+        .and_then(Window::cursor_position)
+        .and_then(|pos| board.cell_at(pos))
+        .and_then(|(x, y)| board.puzzle.tile_at(x, y))
+        .filter(|&tile| tile != 0 && *coloring != TileColoring::None);
+    query.for_each_mut(|(mut sprite, mut transform, mut visibility)| {
+        if visibility.is_visible != hovered.is_some() {
+            visibility.is_visible = hovered.is_some();
+        }
+        if let Some(tile) = hovered {
+            let (x, y) = board.puzzle.goal(tile);
+            let mut color = coloring.tint(tile, &board.puzzle);
+            sprite.color = *color.set_a(0.4);
+            // over the sliders, under the peeked picture
+            transform.translation = board.translation(x, y) + Vec3::Z * 2.0;
+            transform.scale = Vec3::new(board.scale, board.scale, 1.0);
+        }
+    });
+}
@@ -1 +1,58 @@
 //! count the steps and clear when reset or start a challenge.
+use crate::{theme::Theme, ui, TextScale};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// how many moves since the game started or reset
+#[derive(Default)]
+pub struct MoveCounter(pub usize);
+
+// how a slide of some numbers in a line is counted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MoveMetric {
+    // each number is a move
+    #[default]
+    Tile,
+    // the whole line is a move
+    Slice,
+}
+
+impl MoveMetric {
+    pub fn moves(self, count: usize) -> usize {
+        match self {
+            Self::Tile => count,
+            Self::Slice => count.min(1),
+        }
+    }
+}
+
+impl fmt::Display for MoveMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Tile => "tile",
+            Self::Slice => "slice",
+        })
+    }
+}
+
+#[derive(Component)]
+pub struct CounterText;
+
+pub fn spawn(parent: &mut ChildBuilder, theme: &Theme) {
+    ui::text(
+        parent,
+        theme,
+        CounterText,
+        "Moves: 0",
+        TextScale::new(0.03, 0.06),
+    );
+}
+
+pub fn update(counter: Res<MoveCounter>, mut query: Query<&mut Text, With<CounterText>>) {
+    if counter.is_changed() {
+        query.for_each_mut(|mut text| {
+            text.sections[0].value = format!("Moves: {}", counter.0);
+        });
+    }
+}
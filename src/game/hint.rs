@@ -0,0 +1,133 @@
+//! solve the board in background and highlight the number to slide next,
+//! hints are counted apart from the moves.
+use super::{coloring::TileColoring, solving::Solving, status::Status, Board, Slider};
+use crate::{
+    patterns::{PatternFile, PatternFiles},
+    puzzle::{Move, Puzzle},
+    theme::Theme,
+};
+use bevy::{prelude::*, tasks::AsyncComputeTaskPool};
+
+// moves given by the solver, as hints or played back, so that assisted
+// games are told apart on the results
+#[derive(Default)]
+pub struct HintCounter(pub usize);
+
+// the player asks for the next move
+pub struct ShouldHint;
+
+#[derive(Default)]
+pub struct Hint {
+    // the board where the plan starts, and the moves to solve it
+    plan: Option<(Puzzle, Vec<Move>)>,
+    // highlight the first move of plan
+    shown: bool,
+    solving: Option<Solving>,
+}
+
+impl Hint {
+    // the number to highlight on this board
+    fn tile(&self, puzzle: &Puzzle) -> Option<usize> {
+        match &self.plan {
+            Some((start, moves)) if self.shown && start == puzzle => {
+                start.clone().apply(*moves.first()?).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+pub fn request(
+    mut reader: EventReader<ShouldHint>,
+    board: Res<Board>,
+    files: Res<PatternFiles>,
+    assets: Res<Assets<PatternFile>>,
+    pool: Res<AsyncComputeTaskPool>,
+    mut counter: ResMut<HintCounter>,
+    mut hint: ResMut<Hint>,
+) {
+    if reader.iter().count() == 0 || hint.shown {
+        return;
+    }
+    let puzzle = &board.puzzle;
+    if hint.plan.as_ref().is_some_and(|(start, _)| start == puzzle) {
+        hint.shown = true;
+        counter.0 += 1;
+    } else if hint
+        .solving
+        .as_ref()
+        .is_none_or(|solving| solving.puzzle() != puzzle)
+    {
+        hint.solving = Some(Solving::start(puzzle, &files, &assets, &pool));
+    }
+}
+
+// show the hint once solved, if the board is still the same
+pub fn poll(
+    board: Res<Board>,
+    mut counter: ResMut<HintCounter>,
+    mut hint: ResMut<Hint>,
+    mut status: ResMut<Status>,
+) {
+    let result = match &mut hint.solving {
+        Some(solving) => solving.take(),
+        None => return,
+    };
+    if let Some(result) = result {
+        let solving = hint.solving.take().unwrap();
+        match result {
+            Ok(moves) if solving.puzzle() == &board.puzzle => {
+                hint.plan = Some((solving.puzzle().clone(), moves));
+                hint.shown = true;
+                counter.0 += 1;
+            }
+            Ok(_) => (),
+            Err(err) => {
+                warn!("no hint for the board: {}", err);
+                status.0 = format!("No hint: {}", err);
+            }
+        }
+    }
+}
+
+// keep the plan while the player follows it
+pub fn follow(board: Res<Board>, mut hint: ResMut<Hint>) {
+    if !board.is_changed() {
+        return;
+    }
+    let hint = &mut *hint;
+    if let Some((start, moves)) = &mut hint.plan {
+        if start != &board.puzzle {
+            let mut next = start.clone();
+            let followed = moves
+                .first()
+                .is_some_and(|&mv| next.apply(mv).is_ok() && next == board.puzzle);
+            if followed {
+                *start = next;
+                moves.remove(0);
+            } else {
+                hint.plan = None;
+            }
+            hint.shown = false;
+        }
+    }
+}
+
+// tint the slider to move, and the others by their goals if picked
+pub fn highlight(
+    hint: Res<Hint>,
+    board: Res<Board>,
+    theme: Res<Theme>,
+    coloring: Res<TileColoring>,
+    mut query: Query<(&Slider, &mut Sprite)>,
+) {
+    if hint.is_changed() || board.is_changed() || theme.is_changed() || coloring.is_changed() {
+        let tile = hint.tile(&board.puzzle);
+        query.for_each_mut(|(slider, mut sprite)| {
+            sprite.color = match tile == Some(slider.num) {
+                true => theme.palette.hint,
+                false => coloring.tint(slider.num, &board.puzzle),
+            };
+        });
+    }
+}
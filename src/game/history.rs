@@ -0,0 +1,203 @@
+//! moves applied to the board, to be undone and redone.
+use super::{counter::MoveMetric, MoveCounter};
+use crate::puzzle::{Move, Puzzle};
+use serde::{Deserialize, Serialize};
+
+// whether undo and redo are counted as moves, or take back the count
+pub struct CountUndo(pub bool);
+
+impl Default for CountUndo {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+pub enum ShouldRewind {
+    Undo,
+    Redo,
+}
+
+// slides of some numbers in a line
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    undo: Vec<(Move, usize)>,
+    redo: Vec<(Move, usize)>,
+}
+
+impl History {
+    // a new slide forks the history unless it is the one to redo
+    pub fn push(&mut self, mv: Move, count: usize) {
+        if self.redo.last() == Some(&(mv, count)) {
+            self.redo.pop();
+        } else {
+            self.redo.clear();
+        }
+        self.undo.push((mv, count));
+    }
+
+    // moves of each number slid, from the first to the last
+    pub fn moves(&self) -> Vec<Move> {
+        self.undo
+            .iter()
+            .flat_map(|&(mv, count)| [mv].repeat(count))
+            .collect()
+    }
+
+    // whether all slides can be undone and redone from the puzzle
+    pub fn is_valid(&self, puzzle: &Puzzle) -> bool {
+        let (mut back, mut forth) = (puzzle.clone(), puzzle.clone());
+        self.undo
+            .iter()
+            .rev()
+            .all(|&(mv, count)| back.slide(mv.opposite(), count).is_ok())
+            && self
+                .redo
+                .iter()
+                .rev()
+                .all(|&(mv, count)| forth.slide(mv, count).is_ok())
+    }
+
+    pub fn rewind(
+        &mut self,
+        rewind: &ShouldRewind,
+        puzzle: &mut Puzzle,
+        counter: &mut MoveCounter,
+        metric: MoveMetric,
+        count_undo: &CountUndo,
+    ) {
+        match rewind {
+            ShouldRewind::Undo => {
+                if let Some((mv, count)) = self.undo.pop() {
+                    puzzle.slide(mv.opposite(), count).unwrap();
+                    self.redo.push((mv, count));
+                    let moves = metric.moves(count);
+                    counter.0 = match count_undo.0 {
+                        true => counter.0 + moves,
+                        false => counter.0.saturating_sub(moves),
+                    };
+                }
+            }
+            ShouldRewind::Redo => {
+                if let Some((mv, count)) = self.redo.pop() {
+                    puzzle.slide(mv, count).unwrap();
+                    self.undo.push((mv, count));
+                    counter.0 += metric.moves(count);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // slides played on a new board, and the board after them
+    fn played(slides: &[(Move, usize)]) -> (History, Puzzle) {
+        let mut puzzle = Puzzle::new(3, 3);
+        let mut history = History::default();
+        slides.iter().for_each(|&(mv, count)| {
+            puzzle.slide(mv, count).unwrap();
+            history.push(mv, count);
+        });
+        (history, puzzle)
+    }
+
+    #[test]
+    fn push_redoes_or_forks() {
+        let (mut history, mut puzzle) = played(&[(Move::Down, 1), (Move::Right, 2)]);
+        let mut counter = MoveCounter(3);
+        let (metric, count_undo) = (MoveMetric::Tile, CountUndo(false));
+        history.rewind(
+            &ShouldRewind::Undo,
+            &mut puzzle,
+            &mut counter,
+            metric,
+            &count_undo,
+        );
+        assert_eq!(history.redo, [(Move::Right, 2)]);
+        // the same slide again is the one to redo
+        puzzle.slide(Move::Right, 2).unwrap();
+        history.push(Move::Right, 2);
+        assert_eq!(history.undo, [(Move::Down, 1), (Move::Right, 2)]);
+        assert!(history.redo.is_empty());
+        history.rewind(
+            &ShouldRewind::Undo,
+            &mut puzzle,
+            &mut counter,
+            metric,
+            &count_undo,
+        );
+        puzzle.slide(Move::Right, 1).unwrap();
+        history.push(Move::Right, 1);
+        assert_eq!(history.undo, [(Move::Down, 1), (Move::Right, 1)]);
+        assert!(history.redo.is_empty());
+        assert_eq!(history.moves(), [Move::Down, Move::Right]);
+    }
+
+    #[test]
+    fn rewind_counts() {
+        let (mut history, mut puzzle) = played(&[(Move::Down, 1), (Move::Right, 2)]);
+        let mut counter = MoveCounter(3);
+        let count_undo = CountUndo(true);
+        history.rewind(
+            &ShouldRewind::Undo,
+            &mut puzzle,
+            &mut counter,
+            MoveMetric::Tile,
+            &count_undo,
+        );
+        assert_eq!(counter.0, 5);
+        history.rewind(
+            &ShouldRewind::Redo,
+            &mut puzzle,
+            &mut counter,
+            MoveMetric::Tile,
+            &count_undo,
+        );
+        assert_eq!(counter.0, 7);
+        assert_eq!(puzzle, played(&[(Move::Down, 1), (Move::Right, 2)]).1);
+        // undo takes back a move of each slide, down to none
+        let (mut history, mut puzzle) = played(&[(Move::Down, 1), (Move::Right, 2)]);
+        let mut counter = MoveCounter(2);
+        let count_undo = CountUndo(false);
+        (0..3).for_each(|_| {
+            history.rewind(
+                &ShouldRewind::Undo,
+                &mut puzzle,
+                &mut counter,
+                MoveMetric::Slice,
+                &count_undo,
+            );
+        });
+        assert_eq!(counter.0, 0);
+        assert_eq!(puzzle, Puzzle::new(3, 3));
+        history.rewind(
+            &ShouldRewind::Redo,
+            &mut puzzle,
+            &mut counter,
+            MoveMetric::Slice,
+            &count_undo,
+        );
+        assert_eq!(counter.0, 1);
+    }
+
+    #[test]
+    fn valid_only_on_its_board() {
+        let (mut history, mut puzzle) = played(&[(Move::Down, 1), (Move::Right, 2)]);
+        assert!(history.is_valid(&puzzle));
+        assert!(!history.is_valid(&Puzzle::new(3, 3)));
+        let mut counter = MoveCounter::default();
+        history.rewind(
+            &ShouldRewind::Undo,
+            &mut puzzle,
+            &mut counter,
+            MoveMetric::Tile,
+            &CountUndo(true),
+        );
+        // the redo is checked from the board as well
+        assert!(history.is_valid(&puzzle));
+        assert!(!history.is_valid(&played(&[(Move::Down, 1), (Move::Right, 2)]).1));
+        assert!(History::default().is_valid(&Puzzle::new(3, 3)));
+    }
+}
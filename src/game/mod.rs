@@ -1,151 +1,281 @@
 mod buttons;
+mod coloring;
 mod counter;
+mod hint;
+mod history;
+mod picture;
+mod playback;
+mod pointer;
+mod saved;
+mod share;
+mod solving;
+mod status;
+mod timer;
+mod tween;
 
 use super::{CleanUp, GameMode, GameState};
-use crate::animation::AnimationEvent;
+use crate::{
+    animation::AnimationEvent,
+    bindings::{Action, KeyBindings},
+    puzzle::{Move, Puzzle},
+    theme::{Theme, Themed},
+};
 use bevy::{prelude::*, utils::HashMap};
 use buttons::ShouldBeRestored;
+use coloring::Ghost;
+use hint::{Hint, ShouldHint};
+use history::{History, ShouldRewind};
+use picture::{Peek, Picture};
+use playback::{Playback, ShouldPlay};
+use pointer::Pointer;
+use status::Status;
+use std::mem;
+use tween::Tween;
+
+pub use coloring::TileColoring;
+pub use counter::{MoveCounter, MoveMetric};
+pub use hint::HintCounter;
+pub use history::CountUndo;
+pub use picture::PictureMode;
+pub use playback::PlaybackSpeed;
+pub use saved::{Progress, Resume};
+pub use share::ShouldShare;
+pub use timer::{format as format_time, GameTimer};
+pub use tween::{Easing, SlideAnimation};
 
 #[derive(Component)]
 pub struct Game;
 
 impl Game {
+    #[allow(clippy::too_many_arguments)]
     fn enter(
         mut commands: Commands,
         mut writer: EventWriter<AnimationEvent>,
         mode: Res<GameMode>,
-        server: Res<AssetServer>,
+        numbers: Res<ShowNumbers>,
+        picture: Res<PictureMode>,
+        mut resume: ResMut<Resume>,
+        theme: Res<Theme>,
+        time: Res<Time>,
     ) {
         writer.send(AnimationEvent);
-        let mut board = Board::default();
-        board.slider_map = vec![Position::default(); mode.0.pow(2)];
-        (0..mode.0)
-            .flat_map(|x| (0..mode.0).map(move |y| (x, y)))
-            .zip((0..board.slider_map.len()).cycle().skip(1))
-            .for_each(|((y, x), num)| {
-                let pos = Position::from((x, y));
-                board.slider_map[num] = pos;
-                board.position_map.insert(pos, num);
-                commands
-                    // slider image
-                    .spawn_bundle(SpriteBundle {
-                        texture: server.load("images/slider_default.png"),
-                        ..Default::default()
-                    })
-                    .insert(Slider { num })
-                    .insert(Self)
-                    .with_children(|parent| {
-                        parent
-                            // slider number
-                            .spawn_bundle(Text2dBundle {
-                                text: Text::with_section(
-                                    num.to_string(),
-                                    TextStyle {
-                                        color: Color::YELLOW,
-                                        font: server.load("fonts/VictorMono-Bold.ttf"),
-                                        ..Default::default()
-                                    },
-                                    TextAlignment {
-                                        horizontal: HorizontalAlign::Center,
-                                        vertical: VerticalAlign::Center,
-                                    },
-                                ),
-                                transform: Transform {
-                                    translation: Vec3::new(0.0, 0.0, 1.0),
-                                    ..Default::default()
-                                },
-                                visibility: Visibility {
-                                    is_visible: num != 0,
+        // continue the saved game, or shuffle a new one
+        let progress = match mem::take(&mut resume.0).then(Progress::load).flatten() {
+            Some(progress) => progress,
+            None => Progress::new(shuffled(&mode, &time)),
+        };
+        Self::start(&mut commands, &theme, &numbers, &picture, progress);
+        commands.insert_resource(Status::default());
+    }
+
+    // spawn the sliders and insert the resources of a game
+    fn start(
+        commands: &mut Commands,
+        theme: &Theme,
+        numbers: &ShowNumbers,
+        picture: &PictureMode,
+        progress: Progress,
+    ) {
+        let board = Board::new(progress.puzzle);
+        (0..board.puzzle.tiles().len()).for_each(|num| {
+            commands
+                // slider image
+                .spawn_bundle(SpriteBundle {
+                    texture: theme.slider.clone(),
+                    ..Default::default()
+                })
+                .insert(Slider { num })
+                .insert(Themed::Slider)
+                .insert(Tween::default())
+                .insert(Self)
+                .with_children(|parent| {
+                    parent
+                        // slider number
+                        .spawn_bundle(Text2dBundle {
+                            text: Text::with_section(
+                                num.to_string(),
+                                theme.style(Themed::Number),
+                                TextAlignment {
+                                    horizontal: HorizontalAlign::Center,
+                                    vertical: VerticalAlign::Center,
                                 },
+                            ),
+                            transform: Transform {
+                                translation: Vec3::new(0.0, 0.0, 1.0),
                                 ..Default::default()
-                            })
-                            .insert(SliderNumber);
-                    });
-            });
-        // backup the original board
-        commands.insert_resource(BoardOrigin::from(&board));
+                            },
+                            visibility: Visibility {
+                                is_visible: num != 0 && numbers.0,
+                            },
+                            ..Default::default()
+                        })
+                        .insert(SliderNumber)
+                        .insert(Themed::Number);
+                });
+        });
+        // backup the scrambled board for reset
+        commands.insert_resource(BoardOrigin(progress.origin));
         // insert current board
         commands.insert_resource(board);
+        commands.insert_resource(MoveCounter(progress.moves));
+        let mut timer = GameTimer {
+            started: progress.started,
+            ..Default::default()
+        };
+        timer.watch.set_elapsed(progress.elapsed);
+        commands.insert_resource(timer);
+        commands.insert_resource(HintCounter(progress.hints));
+        commands.insert_resource(Hint::default());
+        commands.insert_resource(Playback::default());
+        commands.insert_resource(progress.history);
+        commands.insert_resource(Pointer::default());
+        commands.insert_resource(Picture::new(picture));
+        coloring::spawn(commands, theme);
     }
 
-    fn mouse_system(
-        mouse: Res<Input<MouseButton>>,
-        windows: Res<Windows>,
-        mut board: ResMut<Board>,
+    #[allow(clippy::too_many_arguments)]
+    fn keyboard_system(
+        keyboard: Res<Input<KeyCode>>,
+        bindings: Res<KeyBindings>,
+        mut state: ResMut<State<GameState>>,
+        mut writer: EventWriter<ShouldMove>,
+        mut rewind: EventWriter<ShouldRewind>,
+        mut restore: EventWriter<ShouldBeRestored>,
+        mut hint: EventWriter<ShouldHint>,
+        mut share: EventWriter<ShouldShare>,
+        mut numbers: ResMut<ShowNumbers>,
     ) {
-        // move following mouse
-        if mouse.pressed(MouseButton::Left) {
-            if let Some(window) = windows.get_primary() {
-                if let Some(pos) = window.cursor_position() {
-                    let origin = Position::from((
-                        (pos.x - board.offset) / board.slider_size,
-                        (board.size + board.offset - pos.y) / board.slider_size,
-                    ));
-                    if let Some(&index) = board.position_map.get(&origin) {
-                        for pos in [
-                            Position::new(origin.x + 1, origin.y),
-                            Position::new(origin.x, origin.y + 1),
-                            Position::new(origin.x - 1, origin.y),
-                            Position::new(origin.x, origin.y - 1),
-                        ] {
-                            if let Some(0) = board.position_map.get(&pos) {
-                                *board.position_map.get_mut(&pos).unwrap() = index;
-                                *board.position_map.get_mut(&origin).unwrap() = 0;
-                                board.slider_map.swap(0, index);
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        bindings
+            .just_pressed(&keyboard)
+            .for_each(|action| match action {
+                Action::MoveUp => writer.send(ShouldMove(bindings.direction(Move::Up))),
+                Action::MoveDown => writer.send(ShouldMove(bindings.direction(Move::Down))),
+                Action::MoveLeft => writer.send(ShouldMove(bindings.direction(Move::Left))),
+                Action::MoveRight => writer.send(ShouldMove(bindings.direction(Move::Right))),
+                Action::Undo => rewind.send(ShouldRewind::Undo),
+                Action::Redo => rewind.send(ShouldRewind::Redo),
+                Action::Reset => restore.send(ShouldBeRestored),
+                Action::Back => state.set(GameState::Menu).unwrap_or_default(),
+                Action::Hint => hint.send(ShouldHint),
+                Action::Numbers => numbers.0 = !numbers.0,
+                // held instead of pressed
+                Action::Peek => (),
+                Action::Copy => share.send(ShouldShare::Copy),
+                Action::Paste => share.send(ShouldShare::Paste),
+            });
     }
 
-    fn keyboard_system(keyboard: Res<Input<KeyCode>>, mut board: ResMut<Board>) {
-        keyboard.get_just_released().for_each(|code| {
-            let pos = board.slider_map[0];
-            let origin = match code {
-                KeyCode::Left | KeyCode::A => Position::new(pos.x + 1, pos.y),
-                KeyCode::Right | KeyCode::D => Position::new(pos.x - 1, pos.y),
-                KeyCode::Down | KeyCode::S => Position::new(pos.x, pos.y - 1),
-                KeyCode::Up | KeyCode::W => Position::new(pos.x, pos.y + 1),
-                // ignore any other key
-                _ => return,
+    fn gamepad_system(
+        bindings: Res<KeyBindings>,
+        gamepads: Res<Gamepads>,
+        buttons: Res<Input<GamepadButton>>,
+        axes: Res<Axis<GamepadAxis>>,
+        mut sticks: Local<HashMap<Gamepad, Option<Move>>>,
+        mut writer: EventWriter<ShouldMove>,
+    ) {
+        gamepads.iter().for_each(|&gamepad| {
+            [
+                (GamepadButtonType::DPadLeft, Move::Left),
+                (GamepadButtonType::DPadRight, Move::Right),
+                (GamepadButtonType::DPadDown, Move::Down),
+                (GamepadButtonType::DPadUp, Move::Up),
+            ]
+            .into_iter()
+            .filter(|&(button, _)| buttons.just_pressed(GamepadButton(gamepad, button)))
+            .for_each(|(_, mv)| writer.send(ShouldMove(bindings.direction(mv))));
+            // the stick moves once each time it is pushed aside
+            let axis = |axis| axes.get(GamepadAxis(gamepad, axis)).unwrap_or(0.0);
+            let (x, y) = (
+                axis(GamepadAxisType::LeftStickX),
+                axis(GamepadAxisType::LeftStickY),
+            );
+            let stick = match x.abs() > y.abs() {
+                _ if x.abs().max(y.abs()) < 0.5 => None,
+                true if x > 0.0 => Some(Move::Right),
+                true => Some(Move::Left),
+                false if y > 0.0 => Some(Move::Up),
+                false => Some(Move::Down),
             };
-            if let Some(&index) = board.position_map.get(&origin) {
-                *board.position_map.get_mut(&pos).unwrap() = index;
-                *board.position_map.get_mut(&origin).unwrap() = 0;
-                board.slider_map.swap(0, index);
+            let last = sticks.insert(gamepad, stick).flatten();
+            if let Some(mv) = stick.filter(|&mv| last != Some(mv)) {
+                writer.send(ShouldMove(bindings.direction(mv)));
             }
         });
     }
 
-    fn update(board: Res<Board>, mut query: Query<(&Slider, &mut Transform), With<Slider>>) {
+    // the only place where sliders are moved, whatever the input is
+    #[allow(clippy::too_many_arguments)]
+    fn apply_moves(
+        mut board: ResMut<Board>,
+        mut counter: ResMut<MoveCounter>,
+        mut history: ResMut<History>,
+        mut timer: ResMut<GameTimer>,
+        metric: Res<MoveMetric>,
+        count_undo: Res<CountUndo>,
+        mut reader: EventReader<ShouldMove>,
+        mut slides: EventReader<ShouldSlide>,
+        mut rewind: EventReader<ShouldRewind>,
+    ) {
+        let moves = reader.iter().map(|&ShouldMove(mv)| (mv, 1));
+        let slides = slides.iter().map(|&ShouldSlide(mv, count)| (mv, count));
+        moves.chain(slides).for_each(|(mv, count)| {
+            if board.puzzle.slide(mv, count).is_ok() {
+                history.push(mv, count);
+                counter.0 += metric.moves(count);
+                timer.started = true;
+            }
+        });
+        rewind.iter().for_each(|rewind| {
+            history.rewind(
+                rewind,
+                &mut board.puzzle,
+                &mut counter,
+                *metric,
+                &count_undo,
+            );
+        });
+    }
+
+    // the game is over once every slider is back in order
+    fn check_solved(board: Res<Board>, mut state: ResMut<State<GameState>>) {
+        if board.is_changed() && board.puzzle.is_solved() {
+            // back may be queued in the same frame
+            state.set(GameState::Solved).unwrap_or_default();
+        }
+    }
+
+    fn update(board: Res<Board>, mut query: Query<(&Slider, &mut Tween, &Transform)>) {
         if board.is_changed() {
-            query.for_each_mut(|(slider, mut transform)| {
-                let Position { x, y } = board.slider_map[slider.num];
-                transform.translation = Vec3::new(
-                    board.slider_size * (x as f32 + 0.5) + board.offset,
-                    board.size - board.slider_size * (y as f32 + 0.5) + board.offset,
-                    0.0,
-                );
+            query.for_each_mut(|(slider, mut tween, transform)| {
+                let (x, y) = board.puzzle.position(slider.num);
+                tween.slide((x, y), board.translation(x, y), transform.translation);
             });
         }
     }
 
     fn viewport(
         windows: Res<Windows>,
-        mode: Res<GameMode>,
         mut board: ResMut<Board>,
         mut transform: Query<&mut Transform, With<Slider>>,
         mut text: Query<&mut Text, With<SliderNumber>>,
     ) {
-        if windows.is_changed() {
+        // or the board is restarted
+        if windows.is_changed() || board.is_added() {
             if let Some(window) = windows.get_primary() {
                 let min = window.width().min(window.height());
-                board.offset = min * 0.1;
-                board.size = min * 0.8;
-                board.slider_size = board.size / mode.0 as f32;
+                let (width, height) = (board.puzzle.width(), board.puzzle.height());
+                // fit the board in the middle of a square area
+                let slider_size = min * 0.8 / width.max(height) as f32;
+                let size = Vec2::new(width as f32, height as f32) * slider_size;
+                let offset = Vec2::splat(min * 0.5) - size / 2.0;
+                // most window events leave the layout as it is, the board
+                // is only changed by a new one
+                if board.slider_size == slider_size && board.offset == offset {
+                    return;
+                }
+                board.slider_size = slider_size;
+                board.size = size;
+                board.offset = offset;
                 board.text_size = board.slider_size / 2.0;
                 board.scale = board.slider_size / 128.0;
                 transform.for_each_mut(|mut transform| {
@@ -158,12 +288,39 @@ impl Game {
         }
     }
 
+    // start over with another board, which may be another size
+    #[allow(clippy::type_complexity)]
+    fn wait_restart(
+        mut commands: Commands,
+        mut reader: EventReader<ShouldRestart>,
+        numbers: Res<ShowNumbers>,
+        picture: Res<PictureMode>,
+        theme: Res<Theme>,
+        query: Query<Entity, Or<(With<Slider>, With<Peek>, With<Ghost>)>>,
+    ) {
+        if let Some(ShouldRestart(puzzle)) = reader.iter().last() {
+            query.for_each(|entity| commands.entity(entity).despawn_recursive());
+            let progress = Progress::new(puzzle.clone());
+            Self::start(&mut commands, &theme, &numbers, &picture, progress);
+        }
+    }
+
     fn wait_reset(
         origin: Res<BoardOrigin>,
         mut board: ResMut<Board>,
+        mut counter: ResMut<MoveCounter>,
+        mut timer: ResMut<GameTimer>,
+        mut hints: ResMut<HintCounter>,
+        mut history: ResMut<History>,
         mut reader: EventReader<ShouldBeRestored>,
     ) {
-        reader.iter().for_each(|_| board.restore(&origin));
+        reader.iter().for_each(|_| {
+            board.restore(&origin);
+            counter.0 = 0;
+            *timer = GameTimer::default();
+            hints.0 = 0;
+            *history = History::default();
+        });
     }
 }
 
@@ -173,7 +330,32 @@ impl Plugin for Game {
     fn build(&self, app: &mut App) {
         #[derive(SystemLabel, Clone, Debug, PartialEq, Eq, Hash)]
         struct ButtonsInteraction;
+        #[derive(SystemLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct BoardInput;
+        #[derive(SystemLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct BoardMoved;
+        #[derive(SystemLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct BoardUpdated;
+        #[derive(SystemLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct BoardAnimated;
         app.add_event::<ShouldBeRestored>()
+            .add_event::<ShouldMove>()
+            .add_event::<ShouldSlide>()
+            .add_event::<ShouldHint>()
+            .add_event::<ShouldPlay>()
+            .add_event::<ShouldRewind>()
+            .add_event::<ShouldShare>()
+            .add_event::<ShouldRestart>()
+            .init_resource::<CountUndo>()
+            .init_resource::<MoveMetric>()
+            .init_resource::<SlideAnimation>()
+            .init_resource::<PlaybackSpeed>()
+            .init_resource::<ShowNumbers>()
+            .init_resource::<TileColoring>()
+            .init_resource::<PictureMode>()
+            .init_resource::<Resume>()
+            // a picture may be dropped on any screen
+            .add_system(picture::dropped)
             .add_system_set(
                 SystemSet::on_enter(GameState::Game)
                     .with_system(buttons::setup)
@@ -184,17 +366,61 @@ impl Plugin for Game {
                     // process should be restored event
                     .with_system(buttons::interaction.label(ButtonsInteraction))
                     .with_system(Self::wait_reset.after(ButtonsInteraction))
+                    // solve in background for hints
+                    .with_system(hint::request.after(ButtonsInteraction))
+                    .with_system(hint::poll)
+                    .with_system(hint::follow.after(BoardMoved))
+                    .with_system(hint::highlight.after(BoardMoved))
+                    // play the solution as if keys were pressed
+                    .with_system(playback::control.after(ButtonsInteraction))
+                    .with_system(playback::play.label(BoardInput).after(ButtonsInteraction))
+                    .with_system(buttons::playback)
+                    // copy or paste the board as text
+                    .with_system(share::share.after(ButtonsInteraction).after(BoardInput))
+                    .with_system(Self::wait_restart.after(ButtonsInteraction))
+                    .with_system(status::update)
                     // add mouse support
-                    .with_system(Self::mouse_system)
+                    .with_system(pointer::mouse.label(BoardInput))
+                    // add touch support
+                    .with_system(pointer::touch.label(BoardInput))
                     // add keyboard support
-                    .with_system(Self::keyboard_system)
-                    .with_system(Self::viewport)
-                    .with_system(Self::update),
+                    .with_system(Self::keyboard_system.label(BoardInput))
+                    // add gamepad support
+                    .with_system(Self::gamepad_system.label(BoardInput))
+                    .with_system(Self::apply_moves.label(BoardMoved).after(BoardInput))
+                    .with_system(Self::check_solved.after(BoardMoved))
+                    .with_system(Self::viewport.before(BoardUpdated))
+                    .with_system(Self::update.label(BoardUpdated).after(BoardMoved))
+                    // cut the picture and show it
+                    .with_system(picture::cut)
+                    .with_system(picture::peek.after(BoardUpdated))
+                    .with_system(picture::numbers)
+                    .with_system(coloring::ghost.after(BoardUpdated))
+                    .with_system(tween::animate.label(BoardAnimated).after(BoardUpdated))
+                    .with_system(pointer::carry.after(BoardAnimated))
+                    .with_system(counter::update)
+                    .with_system(timer::tick.after(BoardMoved))
+                    .with_system(timer::update)
+                    .with_system(saved::save_on_close),
             )
-            .add_system_set(SystemSet::on_exit(GameState::Game).with_system(Self::exit));
+            .add_system_set(
+                SystemSet::on_exit(GameState::Game)
+                    .with_system(saved::save)
+                    .with_system(Self::exit),
+            );
     }
 }
 
+// different on each call, but logged for reproducing
+fn shuffled(mode: &GameMode, time: &Time) -> Puzzle {
+    let seed = time.seconds_since_startup().to_bits();
+    info!(
+        "shuffle {}x{} board with seed {}",
+        mode.width, mode.height, seed
+    );
+    Puzzle::shuffled(mode.width, mode.height, seed)
+}
+
 #[derive(Component)]
 struct Slider {
     // slider's number
@@ -204,67 +430,69 @@ struct Slider {
 #[derive(Component)]
 struct SliderNumber;
 
-#[derive(Component, Default)]
-struct Board {
-    offset: f32,
-    scale: f32,
-    size: f32,
-    slider_size: f32,
-    text_size: f32,
-    slider_map: Vec<Position>,
-    position_map: HashMap<Position, usize>,
-}
+// show the numbers on sliders or not
+pub struct ShowNumbers(pub bool);
 
-impl Board {
-    fn restore(&mut self, origin: &BoardOrigin) {
-        self.position_map = origin.position_map.clone();
-        self.slider_map = origin.slider_map.clone();
+impl Default for ShowNumbers {
+    fn default() -> Self {
+        Self(true)
     }
 }
 
-struct BoardOrigin {
-    position_map: HashMap<Position, usize>,
-    slider_map: Vec<Position>,
+#[derive(Component)]
+pub struct Board {
+    // bottom left of the board
+    offset: Vec2,
+    scale: f32,
+    size: Vec2,
+    slider_size: f32,
+    text_size: f32,
+    pub puzzle: Puzzle,
 }
 
-impl From<&Board> for BoardOrigin {
-    fn from(board: &Board) -> Self {
+impl Board {
+    // the layout is unknown until the viewport is set
+    fn new(puzzle: Puzzle) -> Self {
         Self {
-            position_map: board.position_map.clone(),
-            slider_map: board.slider_map.clone(),
+            offset: Vec2::ZERO,
+            scale: 0.0,
+            size: Vec2::ZERO,
+            slider_size: 0.0,
+            text_size: 0.0,
+            puzzle,
         }
     }
-}
-
-// setup a position structure for hash
-// using isize to avoid overflow
-#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
-struct Position {
-    pub x: isize,
-    pub y: isize,
-}
 
-impl Position {
-    // create a new position
-    pub fn new(x: isize, y: isize) -> Self {
-        Self { x, y }
+    fn restore(&mut self, origin: &BoardOrigin) {
+        self.puzzle = origin.0.clone();
     }
-}
 
-impl From<(usize, usize)> for Position {
-    fn from(pos: (usize, usize)) -> Self {
-        Self {
-            x: pos.0 as isize,
-            y: pos.1 as isize,
-        }
+    // the cell under a point of window, if any
+    fn cell_at(&self, pos: Vec2) -> Option<(usize, usize)> {
+        let x = ((pos.x - self.offset.x) / self.slider_size).floor();
+        let y = ((self.size.y + self.offset.y - pos.y) / self.slider_size).floor();
+        (x >= 0.0 && y >= 0.0)
+            .then_some((x as usize, y as usize))
+            .filter(|&(x, y)| x < self.puzzle.width() && y < self.puzzle.height())
     }
-}
 
-impl From<(f32, f32)> for Position {
-    fn from(pos: (f32, f32)) -> Self {
-        Self {
-            x: pos.0 as isize,
-            y: pos.1 as isize,
-        }
+    // the center of a cell in the window
+    fn translation(&self, x: usize, y: usize) -> Vec3 {
+        Vec3::new(
+            self.slider_size * (x as f32 + 0.5) + self.offset.x,
+            self.size.y - self.slider_size * (y as f32 + 0.5) + self.offset.y,
+            0.0,
+        )
     }
 }
+
+struct BoardOrigin(Puzzle);
+
+// a number should slide into the blank
+pub struct ShouldMove(pub Move);
+
+// start over with the board
+pub struct ShouldRestart(pub Puzzle);
+
+// some numbers in a line should slide at once
+pub struct ShouldSlide(pub Move, pub usize);
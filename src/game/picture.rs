@@ -0,0 +1,293 @@
+//! cut a picture into pieces on the sliders, the numbers over them can be
+//! hidden and the whole picture is peeked at while a key is held.
+use super::{status::Status, Board, Game, ShouldRestart, ShowNumbers, Slider, SliderNumber};
+use crate::{
+    bindings::{Action, KeyBindings},
+    GameMode, GameState,
+};
+use bevy::{
+    asset::LoadState,
+    prelude::*,
+    render::{
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageType,
+    },
+    sprite::Rect,
+    window::FileDragAndDrop,
+};
+use serde::{Deserialize, Serialize};
+use std::{ffi::OsStr, fmt, fs, path::Path};
+
+// the path of picture on sliders, none for numbers only
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PictureMode(pub Option<String>);
+
+impl PictureMode {
+    // pictures shipped in assets
+    pub const BUNDLED: [&'static str; 2] =
+        ["images/pictures/sunset.png", "images/pictures/rings.png"];
+
+    // each bundled one in turn, then none
+    pub fn next(&self) -> Self {
+        let index = match &self.0 {
+            None => Some(0),
+            Some(path) => Self::BUNDLED
+                .iter()
+                .position(|bundled| bundled == path)
+                .map(|index| index + 1),
+        };
+        Self(
+            index
+                .and_then(|index| Self::BUNDLED.get(index))
+                .map(|path| path.to_string()),
+        )
+    }
+}
+
+impl fmt::Display for PictureMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.as_deref().map(Path::new).and_then(Path::file_stem) {
+            Some(name) => write!(f, "{}", name.to_string_lossy()),
+            None => f.write_str("none"),
+        }
+    }
+}
+
+// the picture of this game, cut once it is loaded
+pub struct Picture {
+    // the path until it is opened
+    path: Option<String>,
+    image: Option<Handle<Image>>,
+    atlas: Option<Handle<TextureAtlas>>,
+}
+
+impl Picture {
+    pub fn new(mode: &PictureMode) -> Self {
+        Self {
+            path: mode.0.clone(),
+            image: None,
+            atlas: None,
+        }
+    }
+}
+
+// read a picture from disk, instead of assets
+fn open(path: &str, width: usize, height: usize) -> Result<Image, String> {
+    let name = Path::new(path)
+        .file_name()
+        .map_or(path.into(), OsStr::to_string_lossy);
+    let extension = Path::new(path)
+        .extension()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default();
+    let bytes = fs::read(path).map_err(|err| format!("can not read {}: {}", name, err))?;
+    let image = Image::from_buffer(&bytes, ImageType::Extension(extension))
+        .map_err(|err| format!("can not decode {}: {}", name, err))?;
+    crop(&image, width, height).map_err(|err| format!("can not use {}: {}", name, err))
+}
+
+// the largest area of the board's shape in the middle of picture, made
+// smaller by averaging blocks of pixels to fit any gpu
+fn crop(image: &Image, width: usize, height: usize) -> Result<Image, &'static str> {
+    const MAX: usize = 2048;
+    if image.texture_descriptor.format != TextureFormat::Rgba8UnormSrgb {
+        return Err("only 8-bit colors are supported");
+    }
+    let size = image.texture_descriptor.size;
+    let (w, h) = (size.width as usize, size.height as usize);
+    let piece = (w / width).min(h / height);
+    if piece == 0 {
+        return Err("it is smaller than the board");
+    }
+    let (x0, y0) = ((w - piece * width) / 2, (h - piece * height) / 2);
+    let factor = (piece * width.max(height)).div_ceil(MAX);
+    let (cw, ch) = (piece * width / factor, piece * height / factor);
+    let data = (0..cw * ch)
+        .flat_map(|index| {
+            let (x, y) = (x0 + index % cw * factor, y0 + index / cw * factor);
+            let mut sum = [0; 4];
+            (0..factor * factor).for_each(|block| {
+                let pixel = ((y + block / factor) * w + x + block % factor) * 4;
+                (0..4).for_each(|c| sum[c] += image.data[pixel + c] as usize);
+            });
+            sum.map(|sum| (sum / (factor * factor)) as u8)
+        })
+        .collect();
+    Ok(Image::new(
+        Extent3d {
+            width: cw as u32,
+            height: ch as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    ))
+}
+
+// the whole picture over the board
+#[derive(Component)]
+pub struct Peek;
+
+// the largest area of the board's shape in the middle of picture, cut into
+// a piece for each cell, and the area itself after them
+fn atlas(image: Handle<Image>, size: Vec2, width: usize, height: usize) -> TextureAtlas {
+    let cells = Vec2::new(width as f32, height as f32);
+    let piece = (size / cells).min_element().floor();
+    let origin = ((size - cells * piece) / 2.0).floor();
+    let mut atlas = TextureAtlas::new_empty(image, size);
+    (0..width * height).for_each(|index| {
+        let min = origin + Vec2::new((index % width) as f32, (index / width) as f32) * piece;
+        atlas.add_texture(Rect {
+            min,
+            max: min + Vec2::splat(piece),
+        });
+    });
+    atlas.add_texture(Rect {
+        min: origin,
+        max: origin + cells * piece,
+    });
+    atlas
+}
+
+// put the pieces on sliders once the picture is loaded, or keep the
+// numbers and tell why if it can not be
+#[allow(clippy::too_many_arguments)]
+pub fn cut(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut atlases: ResMut<Assets<TextureAtlas>>,
+    board: Res<Board>,
+    mut mode: ResMut<PictureMode>,
+    mut picture: ResMut<Picture>,
+    mut status: ResMut<Status>,
+    mut sliders: Query<(Entity, &Slider, &mut Visibility)>,
+) {
+    let (width, height) = (board.puzzle.width(), board.puzzle.height());
+    let mut failed = |err: String| {
+        warn!("{}", err);
+        status.0 = err;
+        // not to fail again next game
+        mode.0 = None;
+    };
+    if let Some(path) = picture.path.take() {
+        match PictureMode::BUNDLED.contains(&path.as_str()) {
+            true => picture.image = Some(server.load(path.as_str())),
+            false => match open(&path, width, height) {
+                Ok(image) => picture.image = Some(images.add(image)),
+                Err(err) => failed(err),
+            },
+        }
+    }
+    if picture.atlas.is_some() {
+        return;
+    }
+    let handle = match &picture.image {
+        Some(handle) => handle.clone(),
+        None => return,
+    };
+    let image = match images.get(&handle) {
+        Some(image) => image,
+        None => {
+            if server.get_load_state(&handle) == LoadState::Failed {
+                failed("can not load the picture".to_string());
+                picture.image = None;
+            }
+            return;
+        }
+    };
+    let size = image.texture_descriptor.size;
+    let size = Vec2::new(size.width as f32, size.height as f32);
+    let atlas = atlases.add(self::atlas(handle, size, width, height));
+    sliders.for_each_mut(|(entity, slider, mut visibility)| match slider.num {
+        // the blank is empty
+        0 => visibility.is_visible = false,
+        num => {
+            commands.entity(entity).with_children(|parent| {
+                parent.spawn_bundle(SpriteSheetBundle {
+                    sprite: TextureAtlasSprite {
+                        index: num - 1,
+                        // inside the frame of slider
+                        custom_size: Some(Vec2::splat(120.0)),
+                        ..Default::default()
+                    },
+                    texture_atlas: atlas.clone(),
+                    transform: Transform::from_xyz(0.0, 0.0, 0.5),
+                    ..Default::default()
+                });
+            });
+        }
+    });
+    commands
+        .spawn_bundle(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(width * height),
+            texture_atlas: atlas.clone(),
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .insert(Peek)
+        .insert(Game);
+    picture.atlas = Some(atlas);
+}
+
+// show the whole picture over the board while the key is held
+pub fn peek(
+    keyboard: Res<Input<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    board: Res<Board>,
+    mut query: Query<(&mut TextureAtlasSprite, &mut Transform, &mut Visibility), With<Peek>>,
+) {
+    let peeking = bindings.pressed(&keyboard, Action::Peek);
+    query.for_each_mut(|(mut sprite, mut transform, mut visibility)| {
+        if visibility.is_visible != peeking {
+            visibility.is_visible = peeking;
+        }
+        if peeking {
+            sprite.custom_size = Some(board.size);
+            transform.translation = (board.offset + board.size / 2.0).extend(5.0);
+        }
+    });
+}
+
+// show or hide the numbers once toggled
+pub fn numbers(
+    numbers: Res<ShowNumbers>,
+    sliders: Query<&Slider>,
+    mut query: Query<(&Parent, &mut Visibility), With<SliderNumber>>,
+) {
+    if numbers.is_changed() {
+        query.for_each_mut(|(parent, mut visibility)| {
+            if let Ok(slider) = sliders.get(parent.0) {
+                visibility.is_visible = slider.num != 0 && numbers.0;
+            }
+        });
+    }
+}
+
+// play with the picture dropped on the window
+pub fn dropped(
+    mut reader: EventReader<FileDragAndDrop>,
+    mut writer: EventWriter<ShouldRestart>,
+    game: Res<GameMode>,
+    time: Res<Time>,
+    mut mode: ResMut<PictureMode>,
+    mut state: ResMut<State<GameState>>,
+) {
+    let path = reader
+        .iter()
+        .filter_map(|event| match event {
+            FileDragAndDrop::DroppedFile { path_buf, .. } => Some(path_buf),
+            _ => None,
+        })
+        .next_back();
+    if let Some(path) = path {
+        info!("drop picture {}", path.display());
+        mode.0 = Some(path.to_string_lossy().into_owned());
+        match state.current() {
+            GameState::Game => writer.send(ShouldRestart(super::shuffled(&game, &time))),
+            // a game is started on enter, unless leaving is queued
+            _ => state.set(GameState::Game).unwrap_or_default(),
+        }
+    }
+}
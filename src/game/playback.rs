@@ -0,0 +1,121 @@
+//! solve the board and play the solution back one move at a time, the
+//! player takes over by moving any number.
+use super::{solving::Solving, status::Status, Board, HintCounter, ShouldMove};
+use crate::{
+    patterns::{PatternFile, PatternFiles},
+    puzzle::{Move, Puzzle},
+};
+use bevy::{prelude::*, tasks::AsyncComputeTaskPool, utils::Duration};
+
+// time between two moves of playback
+pub struct PlaybackSpeed(pub Duration);
+
+impl Default for PlaybackSpeed {
+    fn default() -> Self {
+        Self(Duration::from_millis(400))
+    }
+}
+
+pub enum ShouldPlay {
+    Solve,
+    // pause or resume
+    Pause,
+    // pause and play the next move only
+    Step,
+    Stop,
+}
+
+#[derive(Default)]
+pub struct Playback {
+    // the board where the remaining moves start
+    plan: Option<(Puzzle, Vec<Move>)>,
+    paused: bool,
+    step: bool,
+    timer: Timer,
+    solving: Option<Solving>,
+}
+
+impl Playback {
+    // solving or playing, even if paused
+    pub fn is_active(&self) -> bool {
+        self.plan.is_some() || self.solving.is_some()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+pub fn control(
+    mut reader: EventReader<ShouldPlay>,
+    board: Res<Board>,
+    files: Res<PatternFiles>,
+    assets: Res<Assets<PatternFile>>,
+    pool: Res<AsyncComputeTaskPool>,
+    mut playback: ResMut<Playback>,
+) {
+    reader.iter().for_each(|event| match event {
+        ShouldPlay::Solve if !playback.is_active() => {
+            *playback = Playback {
+                solving: Some(Solving::start(&board.puzzle, &files, &assets, &pool)),
+                ..Default::default()
+            }
+        }
+        ShouldPlay::Solve => playback.paused = false,
+        ShouldPlay::Pause => playback.paused = !playback.paused,
+        ShouldPlay::Step => {
+            playback.paused = true;
+            playback.step = true;
+        }
+        ShouldPlay::Stop => *playback = Playback::default(),
+    });
+}
+
+// send the moves as if they were keys pressed, each counted as a hint
+pub fn play(
+    time: Res<Time>,
+    speed: Res<PlaybackSpeed>,
+    board: Res<Board>,
+    mut hints: ResMut<HintCounter>,
+    mut playback: ResMut<Playback>,
+    mut writer: EventWriter<ShouldMove>,
+    mut status: ResMut<Status>,
+) {
+    let playback = &mut *playback;
+    if let Some(result) = playback.solving.as_mut().and_then(Solving::take) {
+        let solving = playback.solving.take().unwrap();
+        match result {
+            Ok(moves) if solving.puzzle() == &board.puzzle => {
+                playback.plan = Some((solving.puzzle().clone(), moves));
+                playback.timer = Timer::new(speed.0, true);
+            }
+            // moved while solving
+            Ok(_) => (),
+            Err(err) => {
+                warn!("can not solve the board: {}", err);
+                status.0 = format!("Not solved: {}", err);
+            }
+        }
+    }
+    if let Some((start, moves)) = &mut playback.plan {
+        // the player has taken over
+        if start != &board.puzzle {
+            *playback = Playback::default();
+            return;
+        }
+        playback.timer.tick(time.delta());
+        let due = playback.step || !playback.paused && playback.timer.just_finished();
+        playback.step = false;
+        if due {
+            if let Some(mv) = moves.first().copied() {
+                start.apply(mv).unwrap();
+                moves.remove(0);
+                hints.0 += 1;
+                writer.send(ShouldMove(mv));
+            }
+        }
+        if moves.is_empty() {
+            playback.plan = None;
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! press, drag and release on the board, a click slides the numbers
+//! between it and the blank once, a drag carries them along their way and
+//! slides them if far enough, or they spring back. touches do the same,
+//! and swiping from anywhere else on the board slides the number next to
+//! blank that way, by the mouse too since touches on the web come as the
+//! mouse.
+use super::{tween::Tween, Board, ShouldMove, ShouldSlide, Slider};
+use crate::puzzle::Move;
+use bevy::prelude::*;
+
+// part of a slider to drag over for sliding
+const THRESHOLD: f32 = 0.5;
+// a press moving less pixels than this is a click
+const CLICK: f32 = 4.0;
+// part of a slider to swipe over for sliding
+const SWIPE: f32 = 0.25;
+
+#[derive(Default)]
+pub struct Pointer {
+    // where the press on the board starts and where it is now
+    press: Option<(Vec2, Vec2)>,
+    drag: Option<Drag>,
+}
+
+// numbers in a line being dragged
+struct Drag {
+    // the pressed number, the farthest from blank
+    tile: usize,
+    mv: Move,
+    count: usize,
+    // how far along the way to blank
+    distance: f32,
+    moved: bool,
+}
+
+// what a release does
+pub enum Released {
+    Slide(ShouldSlide),
+    Swipe(ShouldMove),
+}
+
+impl Pointer {
+    // presses off the board, on buttons or aside, are left to them
+    pub fn press(&mut self, board: &Board, pos: Vec2) {
+        let (x, y) = match board.cell_at(pos) {
+            Some(cell) => cell,
+            None => return self.cancel(),
+        };
+        self.press = Some((pos, pos));
+        self.drag = board.puzzle.tile_at(x, y).and_then(|tile| {
+            let (mv, count) = board.puzzle.line_of(tile).ok()?;
+            Some(Drag {
+                tile,
+                mv,
+                count,
+                distance: 0.0,
+                moved: false,
+            })
+        });
+    }
+
+    pub fn drag(&mut self, board: &Board, pos: Vec2) {
+        if let Some((from, to)) = &mut self.press {
+            *to = pos;
+            if let Some(drag) = &mut self.drag {
+                let delta = pos - *from;
+                drag.moved |= delta.length() > CLICK;
+                drag.distance = delta.dot(direction(drag.mv)).clamp(0.0, board.slider_size);
+            }
+        }
+    }
+
+    // the slide if clicked or dragged far enough, or the move if swiped
+    // aside, where it is released if known or the last dragged to
+    pub fn release(&mut self, board: &Board, pos: Option<Vec2>) -> Option<Released> {
+        let (from, to) = self.press.take()?;
+        // dragged but not along the way, which is a swipe
+        let aside = self
+            .drag
+            .as_ref()
+            .is_none_or(|drag| drag.moved && drag.distance < CLICK);
+        let swipe = pos.unwrap_or(to) - from;
+        let slide = self
+            .drag
+            .take()
+            .filter(|drag| drag.is_valid(board))
+            .filter(|drag| !drag.moved || drag.distance > board.slider_size * THRESHOLD);
+        match slide {
+            Some(drag) => Some(Released::Slide(ShouldSlide(drag.mv, drag.count))),
+            None if aside && swipe.length() > board.slider_size * SWIPE => {
+                Some(Released::Swipe(ShouldMove(swiped(swipe))))
+            }
+            None => None,
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl Drag {
+    // the board may be moved by others while dragging
+    fn is_valid(&self, board: &Board) -> bool {
+        board.puzzle.line_of(self.tile) == Ok((self.mv, self.count))
+    }
+
+    // whether the cell is between the pressed number and the blank
+    fn covers(&self, board: &Board, (x, y): (usize, usize)) -> bool {
+        let (tx, ty) = board.puzzle.position(self.tile);
+        let (bx, by) = board.puzzle.blank();
+        let between = |v: usize, a: usize, b: usize| a.min(b) <= v && v <= a.max(b);
+        between(x, tx, bx) && between(y, ty, by)
+    }
+}
+
+pub fn mouse(
+    mouse: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    board: Res<Board>,
+    mut pointer: ResMut<Pointer>,
+    mut slides: EventWriter<ShouldSlide>,
+    mut moves: EventWriter<ShouldMove>,
+) {
+    let pos = windows.get_primary().and_then(Window::cursor_position);
+    if mouse.just_pressed(MouseButton::Left) {
+        if let Some(pos) = pos {
+            pointer.press(&board, pos);
+        }
+    } else if mouse.just_released(MouseButton::Left) {
+        match pointer.release(&board, pos) {
+            Some(Released::Slide(slide)) => slides.send(slide),
+            Some(Released::Swipe(mv)) => moves.send(mv),
+            None => (),
+        }
+    } else if let Some(pos) = pos.filter(|_| mouse.pressed(MouseButton::Left)) {
+        pointer.drag(&board, pos);
+    }
+}
+
+// the first finger works as the mouse
+pub fn touch(
+    touches: Res<Touches>,
+    windows: Res<Windows>,
+    board: Res<Board>,
+    mut pointer: ResMut<Pointer>,
+    mut finger: Local<Option<u64>>,
+    mut slides: EventWriter<ShouldSlide>,
+    mut moves: EventWriter<ShouldMove>,
+) {
+    let window = match windows.get_primary() {
+        Some(window) => window,
+        None => return,
+    };
+    // touches start from the top except on mobile
+    let position = |pos: Vec2| match cfg!(any(target_os = "android", target_os = "ios")) {
+        true => pos,
+        false => Vec2::new(pos.x, window.height() - pos.y),
+    };
+    if finger.is_none() {
+        if let Some(touch) = touches.iter_just_pressed().next() {
+            *finger = Some(touch.id());
+            pointer.press(&board, position(touch.position()));
+        }
+    }
+    let id = match *finger {
+        Some(id) => id,
+        None => return,
+    };
+    if let Some(touch) = touches.get_released(id) {
+        *finger = None;
+        match pointer.release(&board, Some(position(touch.position()))) {
+            Some(Released::Slide(slide)) => slides.send(slide),
+            Some(Released::Swipe(mv)) => moves.send(mv),
+            None => (),
+        }
+    } else if touches.just_cancelled(id) {
+        *finger = None;
+        pointer.cancel();
+    } else if let Some(touch) = touches.get_pressed(id) {
+        pointer.drag(&board, position(touch.position()));
+    }
+}
+
+// keep the dragged numbers under the pointer
+pub fn carry(
+    board: Res<Board>,
+    mut pointer: ResMut<Pointer>,
+    mut query: Query<(&Slider, &mut Tween, &mut Transform)>,
+) {
+    if let Some(drag) = &pointer.drag {
+        if !drag.is_valid(&board) {
+            pointer.drag = None;
+            return;
+        }
+        let offset = direction(drag.mv).extend(0.0) * drag.distance;
+        query.for_each_mut(|(slider, mut tween, mut transform)| {
+            let (x, y) = board.puzzle.position(slider.num);
+            if slider.num != 0 && drag.covers(&board, (x, y)) {
+                let translation = board.translation(x, y) + offset;
+                transform.translation = translation;
+                tween.hold(translation);
+            }
+        });
+    }
+}
+
+// the way of a swipe, whichever is longer of across and along
+fn swiped(swipe: Vec2) -> Move {
+    match swipe.x.abs() > swipe.y.abs() {
+        true if swipe.x > 0.0 => Move::Right,
+        true => Move::Left,
+        false if swipe.y > 0.0 => Move::Up,
+        false => Move::Down,
+    }
+}
+
+// the way a number slides in window
+fn direction(mv: Move) -> Vec2 {
+    match mv {
+        Move::Up => Vec2::Y,
+        Move::Down => -Vec2::Y,
+        Move::Left => -Vec2::X,
+        Move::Right => Vec2::X,
+    }
+}
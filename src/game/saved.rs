@@ -0,0 +1,142 @@
+//! the game in progress, kept when leaving it or closing the app and
+//! continued from the menu.
+use super::{Board, BoardOrigin, GameTimer, HintCounter, History, MoveCounter};
+use crate::{puzzle::Puzzle, storage};
+use bevy::{app::AppExit, prelude::*, utils::Duration};
+use serde::{Deserialize, Serialize};
+
+// continue the saved game on the next enter instead of a new one
+#[derive(Default)]
+pub struct Resume(pub bool);
+
+// a game which is not solved yet
+pub struct Progress {
+    pub puzzle: Puzzle,
+    // the scramble to reset to
+    pub origin: Puzzle,
+    pub moves: usize,
+    pub elapsed: Duration,
+    // the timer runs from the first move on
+    pub started: bool,
+    pub hints: usize,
+    pub history: History,
+}
+
+impl Progress {
+    const KEY: &'static str = "game.ron";
+
+    pub fn new(puzzle: Puzzle) -> Self {
+        Self {
+            origin: puzzle.clone(),
+            puzzle,
+            moves: 0,
+            elapsed: Duration::ZERO,
+            started: false,
+            hints: 0,
+            history: History::default(),
+        }
+    }
+
+    // the saved game, none if there is not one or it is broken
+    pub fn load() -> Option<Self> {
+        let saved = storage::load::<SavedGame>(Self::KEY)?;
+        let (width, height) = (saved.width, saved.height);
+        let progress = Self {
+            puzzle: Puzzle::from_tiles(width, height, saved.tiles).ok()?,
+            origin: Puzzle::from_tiles(width, height, saved.origin).ok()?,
+            moves: saved.moves,
+            elapsed: Duration::from_millis(saved.millis),
+            started: saved.started,
+            hints: saved.hints,
+            history: saved.history,
+        };
+        match progress.history.is_valid(&progress.puzzle) {
+            true => Some(progress),
+            false => {
+                warn!("ignore saved game of invalid history");
+                None
+            }
+        }
+    }
+
+    fn save(&self) {
+        let saved = SavedGame {
+            width: self.puzzle.width(),
+            height: self.puzzle.height(),
+            tiles: self.puzzle.tiles().to_vec(),
+            origin: self.origin.tiles().to_vec(),
+            moves: self.moves,
+            millis: self.elapsed.as_millis() as u64,
+            started: self.started,
+            hints: self.hints,
+            history: self.history.clone(),
+        };
+        storage::save(Self::KEY, &saved);
+    }
+
+    fn clear() {
+        storage::remove(Self::KEY);
+    }
+}
+
+// how a game is stored, checked when loaded
+#[derive(Serialize, Deserialize)]
+struct SavedGame {
+    width: usize,
+    height: usize,
+    tiles: Vec<usize>,
+    origin: Vec<usize>,
+    moves: usize,
+    millis: u64,
+    started: bool,
+    hints: usize,
+    history: History,
+}
+
+// keep the game when leaving, or forget it once solved
+pub fn save(
+    board: Res<Board>,
+    origin: Res<BoardOrigin>,
+    counter: Res<MoveCounter>,
+    timer: Res<GameTimer>,
+    hints: Res<HintCounter>,
+    history: Res<History>,
+) {
+    match board.puzzle.is_solved() {
+        true => Progress::clear(),
+        false => Progress {
+            puzzle: board.puzzle.clone(),
+            origin: origin.0.clone(),
+            moves: counter.0,
+            elapsed: timer.watch.elapsed(),
+            started: timer.started,
+            hints: hints.0,
+            history: history.clone(),
+        }
+        .save(),
+    }
+}
+
+// the app is closed before the game is left, closing the tab sends
+// nothing on the web so the game is kept on each move there instead,
+// the board is also changed by its layout so the last kept is compared
+#[allow(clippy::too_many_arguments)]
+pub fn save_on_close(
+    mut reader: EventReader<AppExit>,
+    mut last: Local<Option<Puzzle>>,
+    board: Res<Board>,
+    origin: Res<BoardOrigin>,
+    counter: Res<MoveCounter>,
+    timer: Res<GameTimer>,
+    hints: Res<HintCounter>,
+    history: Res<History>,
+) {
+    let moved =
+        cfg!(target_arch = "wasm32") && board.is_changed() && last.as_ref() != Some(&board.puzzle);
+    if moved {
+        *last = Some(board.puzzle.clone());
+    }
+    if reader.iter().next().is_some() || moved {
+        save(board, origin, counter, timer, hints, history);
+    }
+}
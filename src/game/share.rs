@@ -0,0 +1,56 @@
+//! copy the board as text to share it, or paste one to play it, the
+//! result is shown in the status.
+use super::{status::Status, Board, BoardOrigin, History, ShouldRestart};
+use crate::{
+    clipboard,
+    puzzle::{self, Puzzle},
+};
+use bevy::prelude::*;
+
+pub enum ShouldShare {
+    Copy,
+    Paste,
+}
+
+pub fn share(
+    mut reader: EventReader<ShouldShare>,
+    mut writer: EventWriter<ShouldRestart>,
+    board: Res<Board>,
+    origin: Res<BoardOrigin>,
+    history: Res<History>,
+    mut status: ResMut<Status>,
+) {
+    let result = match reader.iter().last() {
+        Some(ShouldShare::Copy) => {
+            let notation = board.puzzle.notation();
+            // the way here, for bug reports
+            info!(
+                "copy {} scrambled as {} and moved {}",
+                notation,
+                origin.0.notation(),
+                puzzle::format_moves(&history.moves())
+            );
+            clipboard::copy(&notation).map(|_| "Copied".to_string())
+        }
+        Some(ShouldShare::Paste) => match clipboard::paste() {
+            // boards out of the sizes which can be played are refused here
+            Ok(Some(pasted)) => match pasted.parse::<Puzzle>() {
+                // nothing to play, it would be over at once
+                Ok(puzzle) if puzzle.is_solved() => Err("the board is already solved".to_string()),
+                Ok(puzzle) => {
+                    info!("paste {}", puzzle.notation());
+                    writer.send(ShouldRestart(puzzle));
+                    Ok("Pasted".to_string())
+                }
+                Err(err) => Err(err.to_string()),
+            },
+            Ok(None) => Ok(String::new()),
+            Err(err) => Err(err),
+        },
+        None => return,
+    };
+    status.0 = result.unwrap_or_else(|err| {
+        warn!("can not share the board: {}", err);
+        err
+    });
+}
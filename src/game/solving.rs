@@ -0,0 +1,134 @@
+//! run the solver without blocking the frames, in a task on native and
+//! a slice of it each frame on wasm where tasks run on the main thread.
+use crate::{
+    patterns::{PatternFile, PatternFiles},
+    puzzle::{Move, Puzzle},
+    solver::{Budget, LinearConflict, PatternDatabase, SolveError, Solver},
+};
+use bevy::{prelude::*, tasks::AsyncComputeTaskPool, utils::Duration};
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Mutex;
+
+// give up if the board is too hard to solve
+const BUDGET: Duration = Duration::from_secs(10);
+
+// a solver running in background, cancelled once dropped
+pub struct Solving {
+    puzzle: Puzzle,
+    budget: Budget,
+    running: Running,
+}
+
+// searched a slice each frame
+#[cfg(target_arch = "wasm32")]
+type Running = Result<Search, SolveError>;
+
+// filled by the task once done
+#[cfg(not(target_arch = "wasm32"))]
+type Running = Arc<Mutex<Option<Result<Vec<Move>, SolveError>>>>;
+
+impl Solving {
+    // nodes searched in a frame, a few milliseconds
+    #[cfg(target_arch = "wasm32")]
+    const SLICE: u64 = 10_000;
+
+    // solve by the pattern database of this size if it is loaded
+    pub fn start(
+        puzzle: &Puzzle,
+        files: &PatternFiles,
+        assets: &Assets<PatternFile>,
+        pool: &AsyncComputeTaskPool,
+    ) -> Self {
+        let budget = Budget::timed(BUDGET);
+        let search = match files.find(assets, puzzle.width(), puzzle.height()) {
+            Some(database) => Solver::new(puzzle, database).map(Search::Patterns),
+            None => LinearConflict::new(puzzle.width(), puzzle.height())
+                .and_then(|heuristic| Solver::new(puzzle, heuristic))
+                .map(Search::Linear),
+        };
+        Self {
+            puzzle: puzzle.clone(),
+            running: Self::run(search, &budget, pool),
+            budget,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn run(search: Result<Search, SolveError>, _: &Budget, _: &AsyncComputeTaskPool) -> Running {
+        search
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn run(
+        search: Result<Search, SolveError>,
+        budget: &Budget,
+        pool: &AsyncComputeTaskPool,
+    ) -> Running {
+        let result = Arc::new(Mutex::new(None));
+        let task = {
+            let (budget, result) = (budget.clone(), result.clone());
+            async move {
+                let moves = search.and_then(|mut search| search.run(&budget));
+                *result.lock().unwrap() = Some(moves);
+            }
+        };
+        pool.spawn(task).detach();
+        result
+    }
+
+    // the board being solved
+    pub fn puzzle(&self) -> &Puzzle {
+        &self.puzzle
+    }
+
+    // the result if done, searching a slice more first
+    #[cfg(target_arch = "wasm32")]
+    pub fn take(&mut self) -> Option<Result<Vec<Move>, SolveError>> {
+        let search = match &mut self.running {
+            Ok(search) => search,
+            Err(err) => return Some(Err(*err)),
+        };
+        match search.step(Self::SLICE) {
+            Some(moves) => Some(Ok(moves)),
+            None if self.budget.is_exhausted() => Some(Err(SolveError::Exhausted)),
+            None => None,
+        }
+    }
+
+    // the result if done, only once
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn take(&mut self) -> Option<Result<Vec<Move>, SolveError>> {
+        self.running.lock().unwrap().take()
+    }
+}
+
+impl Drop for Solving {
+    fn drop(&mut self) {
+        self.budget.cancel();
+    }
+}
+
+// the solver by whichever heuristic is there for the board
+enum Search {
+    Patterns(Solver<Arc<PatternDatabase>>),
+    Linear(Solver<LinearConflict>),
+}
+
+impl Search {
+    #[cfg(target_arch = "wasm32")]
+    fn step(&mut self, nodes: u64) -> Option<Vec<Move>> {
+        match self {
+            Self::Patterns(solver) => solver.step(nodes),
+            Self::Linear(solver) => solver.step(nodes),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn run(&mut self, budget: &Budget) -> Result<Vec<Move>, SolveError> {
+        match self {
+            Self::Patterns(solver) => solver.run(budget),
+            Self::Linear(solver) => solver.run(budget),
+        }
+    }
+}
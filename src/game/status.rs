@@ -0,0 +1,20 @@
+//! a line under the buttons telling how the last action went, or what
+//! went wrong with it.
+use crate::{theme::Theme, ui, TextScale};
+use bevy::prelude::*;
+
+#[derive(Default)]
+pub struct Status(pub String);
+
+#[derive(Component)]
+pub struct StatusText;
+
+pub fn spawn(parent: &mut ChildBuilder, theme: &Theme) {
+    ui::text(parent, theme, StatusText, "", TextScale::new(0.02, 0.04));
+}
+
+pub fn update(status: Res<Status>, mut query: Query<&mut Text, With<StatusText>>) {
+    if status.is_changed() {
+        query.for_each_mut(|mut text| text.sections[0].value = status.0.clone());
+    }
+}
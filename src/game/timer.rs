@@ -0,0 +1,50 @@
+//! time the game from the first move, pause when the player is away.
+use crate::{floating::Popup, theme::Theme, ui, TextScale};
+use bevy::{core::Stopwatch, prelude::*, utils::Duration};
+
+// time spent on solving since the first move
+#[derive(Default)]
+pub struct GameTimer {
+    pub watch: Stopwatch,
+    // kept once the first move is made, even if it is undone
+    pub started: bool,
+}
+
+#[derive(Component)]
+pub struct TimerText;
+
+pub fn spawn(parent: &mut ChildBuilder, theme: &Theme) {
+    let text = format!("Time: {}", format(Duration::ZERO));
+    ui::text(parent, theme, TimerText, &text, TextScale::new(0.03, 0.06));
+}
+
+pub fn tick(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    popup: Res<Popup>,
+    mut timer: ResMut<GameTimer>,
+) {
+    let focused = windows.get_primary().is_none_or(Window::is_focused);
+    if timer.started && focused && !popup.is_open() {
+        timer.watch.tick(time.delta());
+    }
+}
+
+pub fn update(timer: Res<GameTimer>, mut query: Query<&mut Text, With<TimerText>>) {
+    if timer.is_changed() {
+        query.for_each_mut(|mut text| {
+            text.sections[0].value = format!("Time: {}", format(timer.watch.elapsed()));
+        });
+    }
+}
+
+// minutes, seconds and tenths like 01:23.4
+pub fn format(duration: Duration) -> String {
+    let tenths = duration.as_millis() / 100;
+    format!(
+        "{:02}:{:02}.{}",
+        tenths / 600,
+        tenths / 10 % 60,
+        tenths % 10
+    )
+}
@@ -0,0 +1,115 @@
+//! slide the sliders to their cells over time, a slider already sliding
+//! turns to its new cell from where it is, so no move is ever lost.
+use bevy::{prelude::*, utils::Duration};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// how the sliders slide
+pub struct SlideAnimation {
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl Default for SlideAnimation {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_millis(120),
+            easing: Easing::EaseOut,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    EaseOut,
+    EaseInOut,
+    // go a little too far and come back
+    Back,
+}
+
+impl Easing {
+    pub const ALL: [Self; 4] = [Self::Linear, Self::EaseOut, Self::EaseInOut, Self::Back];
+
+    pub fn next(self) -> Self {
+        crate::cycle(&Self::ALL, self)
+    }
+
+    // progress of position by progress of time, both from 0 to 1
+    pub fn ease(self, t: f32) -> f32 {
+        match self {
+            Self::Linear => t,
+            Self::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+            Self::Back => {
+                let (c1, c3) = (1.70158, 2.70158);
+                1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Easing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Linear => "linear",
+            Self::EaseOut => "ease out",
+            Self::EaseInOut => "ease in out",
+            Self::Back => "back",
+        })
+    }
+}
+
+#[derive(Component, Default)]
+pub struct Tween {
+    // the cell to slide to, none until placed
+    cell: Option<(usize, usize)>,
+    from: Vec3,
+    to: Vec3,
+    elapsed: Duration,
+}
+
+impl Tween {
+    // slide from the current translation if the cell is changed,
+    // or place it at once if only the board is resized
+    pub fn slide(&mut self, cell: (usize, usize), to: Vec3, current: Vec3) {
+        if self.cell == Some(cell) {
+            if self.to != to {
+                self.from = to;
+            }
+        } else if self.cell.is_some() {
+            self.from = current;
+            self.elapsed = Duration::ZERO;
+        } else {
+            self.from = to;
+        }
+        self.cell = Some(cell);
+        self.to = to;
+    }
+
+    // slide from here once released
+    pub fn hold(&mut self, current: Vec3) {
+        self.from = current;
+        self.elapsed = Duration::ZERO;
+    }
+}
+
+pub fn animate(
+    time: Res<Time>,
+    animation: Res<SlideAnimation>,
+    mut query: Query<(&mut Tween, &mut Transform)>,
+) {
+    query.for_each_mut(|(mut tween, mut transform)| {
+        if transform.translation != tween.to {
+            tween.elapsed += time.delta();
+            let t = match animation.duration.is_zero() {
+                true => 1.0,
+                false => (tween.elapsed.as_secs_f32() / animation.duration.as_secs_f32()).min(1.0),
+            };
+            transform.translation = match t < 1.0 {
+                true => tween.from.lerp(tween.to, animation.easing.ease(t)),
+                false => tween.to,
+            };
+        }
+    });
+}
@@ -0,0 +1,214 @@
+use super::{CleanUp, GameState, TextLabel, TextScale};
+use crate::{
+    animation::AnimationEvent,
+    bindings::{self, Action, KeyBindings, Shortcut},
+    theme::{Theme, Themed},
+    ui::{self, button, row},
+};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct GameBindings;
+
+impl GameBindings {
+    fn enter(
+        mut commands: Commands,
+        mut writer: EventWriter<AnimationEvent>,
+        bindings: Res<KeyBindings>,
+        theme: Res<Theme>,
+    ) {
+        writer.send(AnimationEvent);
+        commands.insert_resource(Rebinding::default());
+        commands
+            // root node
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    // build ui from top to bottom
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::ColumnReverse,
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    ..Default::default()
+                },
+                visibility: Visibility { is_visible: false },
+                ..Default::default()
+            })
+            .insert(Self)
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(TextBundle {
+                        style: Style {
+                            margin: Rect::all(Val::Auto),
+                            ..Default::default()
+                        },
+                        text: theme.text("Key Bindings", Themed::Text),
+                        ..Default::default()
+                    })
+                    .insert(TextLabel::with_section(TextScale::new(0.05, 0.1)))
+                    .insert(Themed::Text);
+                // an action and its keys in each row
+                Action::ALL.into_iter().for_each(|action| {
+                    row(parent, 60.0, 6.0, |parent| {
+                        parent
+                            .spawn_bundle(TextBundle {
+                                style: Style {
+                                    size: Size::new(Val::Percent(40.0), Val::Auto),
+                                    ..Default::default()
+                                },
+                                text: theme.text(&action.to_string(), Themed::Text),
+                                ..Default::default()
+                            })
+                            .insert(TextLabel::with_section(TextScale::new(0.025, 0.05)))
+                            .insert(Themed::Text);
+                        [0, 1].into_iter().for_each(|slot| {
+                            let label = Label::Key(action, slot);
+                            button(
+                                parent,
+                                &theme,
+                                label,
+                                &label.text(&bindings, None),
+                                28.0,
+                                ui::SMALL,
+                            );
+                        });
+                    });
+                });
+                row(parent, 60.0, 6.0, |parent| {
+                    let label = Label::Direction;
+                    button(
+                        parent,
+                        &theme,
+                        label,
+                        &label.text(&bindings, None),
+                        100.0,
+                        ui::SMALL,
+                    );
+                });
+                row(parent, 60.0, 6.0, |parent| {
+                    button(parent, &theme, Label::Defaults, "Defaults", 48.0, ui::SMALL);
+                    button(parent, &theme, Label::Back, "Back", 48.0, ui::SMALL);
+                });
+            });
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn update(
+        mut bindings: ResMut<KeyBindings>,
+        mut rebinding: ResMut<Rebinding>,
+        mut state: ResMut<State<GameState>>,
+        theme: Res<Theme>,
+        mut query: Query<(&Interaction, &Label, &mut UiColor), (Changed<Interaction>, With<Label>)>,
+    ) {
+        query.for_each_mut(|(interaction, label, mut color)| match interaction {
+            Interaction::Clicked => match *label {
+                Label::Key(action, slot) => rebinding.0 = Some((action, slot)),
+                Label::Direction => bindings.move_blank = !bindings.move_blank,
+                Label::Defaults => *bindings = KeyBindings::default(),
+                Label::Back => state.set(GameState::Settings).unwrap(),
+            },
+            Interaction::Hovered => *color = theme.palette.hovered.into(),
+            Interaction::None => *color = theme.palette.button.into(),
+        });
+    }
+
+    // bind the next key pressed, or unbind by delete (escape quits)
+    fn capture(
+        keyboard: Res<Input<KeyCode>>,
+        mut bindings: ResMut<KeyBindings>,
+        mut rebinding: ResMut<Rebinding>,
+    ) {
+        if let Some((action, slot)) = rebinding.0 {
+            let modifiers = [
+                KeyCode::LControl,
+                KeyCode::RControl,
+                KeyCode::LShift,
+                KeyCode::RShift,
+                KeyCode::LAlt,
+                KeyCode::RAlt,
+            ];
+            let key = keyboard
+                .get_just_pressed()
+                .find(|key| !modifiers.contains(key));
+            if let Some(&key) = key {
+                let shortcut = Shortcut {
+                    key,
+                    control: bindings::is_control_pressed(&keyboard),
+                };
+                let shortcut = (key != KeyCode::Delete).then_some(shortcut);
+                bindings.bind(action, slot, shortcut);
+                rebinding.0 = None;
+            }
+        }
+    }
+
+    // show the keys once changed
+    fn text_system(
+        bindings: Res<KeyBindings>,
+        rebinding: Res<Rebinding>,
+        mut query: Query<(&Label, &mut Text), Without<Interaction>>,
+    ) {
+        if bindings.is_changed() || rebinding.is_changed() {
+            query.for_each_mut(|(label, mut text)| {
+                text.sections[0].value = label.text(&bindings, rebinding.0);
+            });
+        }
+    }
+
+    fn save(bindings: Res<KeyBindings>) {
+        bindings.save();
+    }
+}
+
+impl CleanUp<Self> for GameBindings {}
+
+impl Plugin for GameBindings {
+    fn build(&self, app: &mut App) {
+        #[derive(SystemLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct KeyCaptured;
+        app.insert_resource(KeyBindings::load())
+            .add_system_set(SystemSet::on_enter(GameState::Bindings).with_system(Self::enter))
+            .add_system_set(
+                SystemSet::on_update(GameState::Bindings)
+                    // before a key pressing the button is bound
+                    .with_system(Self::capture.label(KeyCaptured))
+                    .with_system(Self::update.after(KeyCaptured))
+                    .with_system(Self::text_system),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::Bindings)
+                    .with_system(Self::save)
+                    .with_system(Self::exit),
+            );
+    }
+}
+
+// the key waiting to be bound
+#[derive(Default)]
+struct Rebinding(Option<(Action, usize)>);
+
+// labels for each botton, and the text of them
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum Label {
+    Key(Action, usize),
+    Direction,
+    Defaults,
+    Back,
+}
+
+impl Label {
+    fn text(self, bindings: &KeyBindings, rebinding: Option<(Action, usize)>) -> String {
+        match self {
+            Self::Key(action, slot) if rebinding == Some((action, slot)) => "...".to_string(),
+            Self::Key(action, slot) => match bindings.keys.get(&action).and_then(|keys| keys[slot])
+            {
+                Some(key) => key.to_string(),
+                None => "-".to_string(),
+            },
+            Self::Direction => match bindings.move_blank {
+                true => "Arrows move the blank".to_string(),
+                false => "Arrows move the number".to_string(),
+            },
+            Self::Defaults => "Defaults".to_string(),
+            Self::Back => "Back".to_string(),
+        }
+    }
+}
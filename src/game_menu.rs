@@ -1,5 +1,10 @@
-use super::{CleanUp, GameMode, GameState, TextLabel, TextScale};
-use crate::animation::AnimationEvent;
+use super::{CleanUp, GameState};
+use crate::{
+    animation::AnimationEvent,
+    game::{Progress, Resume},
+    theme::Theme,
+    ui::{self, button, row},
+};
 
 use bevy::prelude::*;
 
@@ -7,12 +12,10 @@ use bevy::prelude::*;
 pub struct GameMenu;
 
 impl GameMenu {
-    fn enter(
-        mut commands: Commands,
-        mut writer: EventWriter<AnimationEvent>,
-        server: Res<AssetServer>,
-    ) {
+    fn enter(mut commands: Commands, mut writer: EventWriter<AnimationEvent>, theme: Res<Theme>) {
         writer.send(AnimationEvent);
+        // continue only if there is a game to continue
+        let saved = Progress::load().is_some();
         commands
             // root node
             .spawn_bundle(NodeBundle {
@@ -29,68 +32,41 @@ impl GameMenu {
             .with_children(|parent| {
                 [
                     // build each botton
-                    (Label::Mode3x3, "3x3"),
-                    (Label::Mode4x4, "4x4"),
+                    (Label::Continue, "Continue"),
+                    (Label::Start, "Start"),
+                    (Label::Settings, "Settings"),
                     (Label::Back, "Back"),
                 ]
                 .into_iter()
+                .filter(|&(label, _)| saved || !matches!(label, Label::Continue))
                 .for_each(|(label, text)| {
-                    parent
-                        .spawn_bundle(ButtonBundle {
-                            style: Style {
-                                align_items: AlignItems::Center,
-                                justify_content: JustifyContent::Center,
-                                margin: Rect::all(Val::Auto),
-                                size: Size::new(Val::Percent(50.0), Val::Percent(20.0)),
-                                ..Default::default()
-                            },
-                            ..Default::default()
-                        })
-                        .insert(label)
-                        .with_children(|parent| {
-                            parent
-                                .spawn_bundle(TextBundle {
-                                    text: Text::with_section(
-                                        text,
-                                        TextStyle {
-                                            color: Color::OLIVE,
-                                            font: server.load("fonts/VictorMono-BoldItalic.ttf"),
-                                            ..Default::default()
-                                        },
-                                        Default::default(),
-                                    ),
-                                    ..Default::default()
-                                })
-                                .insert(TextLabel::with_section(TextScale::new(0.05, 0.1)));
-                        });
+                    row(parent, 50.0, 20.0, |parent| {
+                        button(parent, &theme, label, text, 100.0, ui::LARGE);
+                    });
                 });
             });
     }
 
+    #[allow(clippy::type_complexity)]
     fn update(
-        mut commands: Commands,
+        theme: Res<Theme>,
+        mut resume: ResMut<Resume>,
         mut state: ResMut<State<GameState>>,
         mut query: Query<(&Interaction, &Label, &mut UiColor), (Changed<Interaction>, With<Label>)>,
     ) {
         query.for_each_mut(|(interaction, label, mut color)| match interaction {
-            Interaction::Clicked => {
+            Interaction::Clicked => match label {
                 // set game state
-                state
-                    .set(match label {
-                        Label::Mode3x3 => {
-                            commands.insert_resource(GameMode(3));
-                            GameState::Game
-                        }
-                        Label::Mode4x4 => {
-                            commands.insert_resource(GameMode(4));
-                            GameState::Game
-                        }
-                        Label::Back => GameState::default(),
-                    })
-                    .unwrap();
-            }
-            Interaction::Hovered => *color = Color::GOLD.into(),
-            Interaction::None => *color = Color::YELLOW.into(),
+                Label::Continue => {
+                    resume.0 = true;
+                    state.set(GameState::Game).unwrap();
+                }
+                Label::Start => state.set(GameState::Game).unwrap(),
+                Label::Settings => state.set(GameState::Settings).unwrap(),
+                Label::Back => state.set(GameState::default()).unwrap(),
+            },
+            Interaction::Hovered => *color = theme.palette.hovered.into(),
+            Interaction::None => *color = theme.palette.button.into(),
         });
     }
 }
@@ -106,9 +82,10 @@ impl Plugin for GameMenu {
 }
 
 // labels for each botton
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 enum Label {
-    Mode3x3,
-    Mode4x4,
+    Continue,
+    Start,
+    Settings,
     Back,
 }
@@ -0,0 +1,304 @@
+use super::{CleanUp, GameMode, GameState, TextLabel, TextScale};
+use crate::{
+    animation::AnimationEvent,
+    game::{
+        CountUndo, MoveMetric, PictureMode, PlaybackSpeed, ShowNumbers, SlideAnimation,
+        TileColoring,
+    },
+    settings::Volume,
+    theme::{Theme, ThemeName, Themed},
+    ui::{self, button, row},
+};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::Duration};
+use std::marker::PhantomData;
+
+#[derive(Component)]
+pub struct GameSettings;
+
+impl GameSettings {
+    fn enter(
+        mut commands: Commands,
+        mut writer: EventWriter<AnimationEvent>,
+        options: Options,
+        theme: Res<Theme>,
+    ) {
+        writer.send(AnimationEvent);
+        let text_of = |label: Label| label.text(&options);
+        commands
+            // root node
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    // build ui from top to bottom
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::ColumnReverse,
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    ..Default::default()
+                },
+                visibility: Visibility { is_visible: false },
+                ..Default::default()
+            })
+            .insert(Self)
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(TextBundle {
+                        style: Style {
+                            margin: Rect::all(Val::Auto),
+                            ..Default::default()
+                        },
+                        text: theme.text("Settings", Themed::Text),
+                        ..Default::default()
+                    })
+                    .insert(TextLabel::with_section(TextScale::new(0.05, 0.1)))
+                    .insert(Themed::Text);
+                // a value between two buttons to change it
+                Value::ALL.into_iter().for_each(|value| {
+                    row(parent, 50.0, 8.0, |parent| {
+                        button(parent, &theme, Label::Less(value), "-", 20.0, ui::SMALL);
+                        let label = Label::Value(value);
+                        parent
+                            .spawn_bundle(TextBundle {
+                                text: theme.text(&text_of(label), Themed::Text),
+                                ..Default::default()
+                            })
+                            .insert(TextLabel::with_section(TextScale::new(0.03, 0.06)))
+                            .insert(Themed::Text)
+                            .insert(label);
+                        button(parent, &theme, Label::More(value), "+", 20.0, ui::SMALL);
+                    });
+                });
+                // toggles and the other buttons, two in a row
+                [
+                    [Label::Easing, Label::Metric],
+                    [Label::Theme, Label::Picture],
+                    [Label::Numbers, Label::Coloring],
+                    [Label::Undo, Label::Bindings],
+                ]
+                .into_iter()
+                .for_each(|labels| {
+                    row(parent, 50.0, 8.0, |parent| {
+                        labels.into_iter().for_each(|label| {
+                            button(parent, &theme, label, &text_of(label), 48.0, ui::SMALL);
+                        });
+                    });
+                });
+                row(parent, 24.0, 8.0, |parent| {
+                    button(parent, &theme, Label::Back, "Back", 100.0, ui::SMALL);
+                });
+            });
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn update(
+        mut options: Options,
+        mut state: ResMut<State<GameState>>,
+        theme: Res<Theme>,
+        mut query: Query<(&Interaction, &Label, &mut UiColor), (Changed<Interaction>, With<Label>)>,
+    ) {
+        let Options {
+            mode,
+            animation,
+            metric,
+            name,
+            numbers,
+            coloring,
+            picture,
+            volume,
+            count_undo,
+            playback,
+            ..
+        } = &mut options;
+        query.for_each_mut(|(interaction, label, mut color)| match interaction {
+            Interaction::Clicked => match *label {
+                Label::Less(value) | Label::More(value) => {
+                    let more = matches!(label, Label::More(_));
+                    match value {
+                        Value::Width => {
+                            mode.width = step(mode.width, 1, GameMode::MIN, GameMode::MAX, more)
+                        }
+                        Value::Height => {
+                            mode.height = step(mode.height, 1, GameMode::MIN, GameMode::MAX, more)
+                        }
+                        Value::Speed => {
+                            let millis = animation.duration.as_millis() as usize;
+                            let millis = step(millis, 40, 0, 400, more);
+                            animation.duration = Duration::from_millis(millis as u64);
+                        }
+                        Value::Playback => {
+                            let millis = playback.0.as_millis() as usize;
+                            let millis = step(millis, 100, 100, 1000, more);
+                            playback.0 = Duration::from_millis(millis as u64);
+                        }
+                        Value::Volume => {
+                            let max = Volume::MAX as usize;
+                            volume.0 = step(volume.0 as usize, 10, 0, max, more) as u8;
+                        }
+                    }
+                }
+                Label::Easing => animation.easing = animation.easing.next(),
+                Label::Metric => {
+                    **metric = match **metric {
+                        MoveMetric::Tile => MoveMetric::Slice,
+                        MoveMetric::Slice => MoveMetric::Tile,
+                    }
+                }
+                Label::Theme => **name = name.next(),
+                Label::Numbers => numbers.0 = !numbers.0,
+                Label::Coloring => **coloring = coloring.next(),
+                Label::Picture => **picture = picture.next(),
+                Label::Undo => count_undo.0 = !count_undo.0,
+                Label::Bindings => state.set(GameState::Bindings).unwrap(),
+                Label::Back => state.set(GameState::Menu).unwrap(),
+                Label::Value(_) => (),
+            },
+            Interaction::Hovered => *color = theme.palette.hovered.into(),
+            Interaction::None => *color = theme.palette.button.into(),
+        });
+    }
+
+    // show the picked values and options
+    fn text_system(options: Options, mut query: Query<(&Label, &mut Text), Without<Interaction>>) {
+        if options.is_changed() {
+            query.for_each_mut(|(label, mut text)| {
+                text.sections[0].value = label.text(&options);
+            });
+        }
+    }
+}
+
+impl CleanUp<Self> for GameSettings {}
+
+impl Plugin for GameSettings {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Settings).with_system(Self::enter))
+            .add_system_set(
+                SystemSet::on_update(GameState::Settings)
+                    .with_system(Self::update)
+                    .with_system(Self::text_system),
+            )
+            .add_system_set(SystemSet::on_exit(GameState::Settings).with_system(Self::exit));
+    }
+}
+
+// the value one step less or more, kept in the range
+fn step(value: usize, step: usize, min: usize, max: usize, more: bool) -> usize {
+    match more {
+        true => (value + step).min(max),
+        false => value.saturating_sub(step).max(min),
+    }
+}
+
+// everything picked in this screen
+#[derive(SystemParam)]
+struct Options<'w, 's> {
+    mode: ResMut<'w, GameMode>,
+    animation: ResMut<'w, SlideAnimation>,
+    metric: ResMut<'w, MoveMetric>,
+    name: ResMut<'w, ThemeName>,
+    numbers: ResMut<'w, ShowNumbers>,
+    coloring: ResMut<'w, TileColoring>,
+    picture: ResMut<'w, PictureMode>,
+    volume: ResMut<'w, Volume>,
+    count_undo: ResMut<'w, CountUndo>,
+    playback: ResMut<'w, PlaybackSpeed>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+impl Options<'_, '_> {
+    fn is_changed(&self) -> bool {
+        self.mode.is_changed()
+            || self.animation.is_changed()
+            || self.metric.is_changed()
+            || self.name.is_changed()
+            || self.numbers.is_changed()
+            || self.coloring.is_changed()
+            || self.picture.is_changed()
+            || self.volume.is_changed()
+            || self.count_undo.is_changed()
+            || self.playback.is_changed()
+    }
+}
+
+// values picked by less and more buttons
+#[derive(Clone, Copy)]
+enum Value {
+    Width,
+    Height,
+    Speed,
+    // time between moves of auto-solve
+    Playback,
+    Volume,
+}
+
+impl Value {
+    const ALL: [Self; 5] = [
+        Self::Width,
+        Self::Height,
+        Self::Speed,
+        Self::Playback,
+        Self::Volume,
+    ];
+}
+
+// labels for each botton and value, and the text of them
+#[derive(Component, Clone, Copy)]
+enum Label {
+    Less(Value),
+    More(Value),
+    Value(Value),
+    Easing,
+    Metric,
+    Theme,
+    Picture,
+    Numbers,
+    Coloring,
+    Undo,
+    Bindings,
+    Back,
+}
+
+impl Label {
+    fn text(self, options: &Options) -> String {
+        let Options {
+            mode,
+            animation,
+            metric,
+            name,
+            numbers,
+            coloring,
+            picture,
+            volume,
+            count_undo,
+            playback,
+            ..
+        } = options;
+        match self {
+            Self::Less(_) => "-".to_string(),
+            Self::More(_) => "+".to_string(),
+            Self::Value(Value::Width) => format!("Width {}", mode.width),
+            Self::Value(Value::Height) => format!("Height {}", mode.height),
+            Self::Value(Value::Speed) => match animation.duration.as_millis() {
+                0 => "Slide at once".to_string(),
+                millis => format!("Slide {}ms", millis),
+            },
+            Self::Value(Value::Playback) => format!("Solve {}ms", playback.0.as_millis()),
+            Self::Value(Value::Volume) => format!("Volume {}%", volume.0),
+            Self::Easing => format!("Slide: {}", animation.easing),
+            Self::Metric => format!("Metric: {}", **metric),
+            Self::Theme => format!("Theme: {}", **name),
+            Self::Picture => format!("Picture: {}", **picture),
+            Self::Numbers => match numbers.0 {
+                true => "Numbers: on".to_string(),
+                false => "Numbers: off".to_string(),
+            },
+            Self::Coloring => format!("Colors: {}", **coloring),
+            // undo and redo are counted as moves, or undo takes them back
+            Self::Undo => match count_undo.0 {
+                true => "Undo: counted".to_string(),
+                false => "Undo: takes back".to_string(),
+            },
+            Self::Bindings => "Keys".to_string(),
+            Self::Back => "Back".to_string(),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use super::{CleanUp, GameState, TextScale};
+use crate::{
+    animation::AnimationEvent,
+    game::{format_time, Board, GameTimer, HintCounter, MoveCounter},
+    theme::Theme,
+    ui::{self, button, row},
+};
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct GameSolved;
+
+impl GameSolved {
+    fn enter(
+        mut commands: Commands,
+        mut writer: EventWriter<AnimationEvent>,
+        board: Res<Board>,
+        counter: Res<MoveCounter>,
+        timer: Res<GameTimer>,
+        hints: Res<HintCounter>,
+        theme: Res<Theme>,
+    ) {
+        writer.send(AnimationEvent);
+        commands
+            // root node
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    // build ui from top to bottom
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::ColumnReverse,
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    ..Default::default()
+                },
+                visibility: Visibility { is_visible: false },
+                ..Default::default()
+            })
+            .insert(Self)
+            .with_children(|parent| {
+                // results of this game
+                let (width, height) = (board.puzzle.width(), board.puzzle.height());
+                let title = format!("{}x{} Solved!", width, height);
+                ui::heading(parent, &theme, &title, TextScale::new(0.08, 0.16));
+                // how well the player did
+                let elapsed = format_time(timer.watch.elapsed());
+                let results = format!(
+                    "Moves: {}  Time: {}  Hints: {}",
+                    counter.0, elapsed, hints.0
+                );
+                ui::heading(parent, &theme, &results, TextScale::new(0.04, 0.08));
+                [
+                    // build each botton
+                    (Label::Again, "Play again"),
+                    (Label::Back, "Back to menu"),
+                ]
+                .into_iter()
+                .for_each(|(label, text)| {
+                    row(parent, 50.0, 20.0, |parent| {
+                        button(parent, &theme, label, text, 100.0, ui::LARGE);
+                    });
+                });
+            });
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn update(
+        theme: Res<Theme>,
+        mut state: ResMut<State<GameState>>,
+        mut query: Query<(&Interaction, &Label, &mut UiColor), (Changed<Interaction>, With<Label>)>,
+    ) {
+        query.for_each_mut(|(interaction, label, mut color)| match interaction {
+            Interaction::Clicked => state
+                .set(match label {
+                    // same mode with a new board
+                    Label::Again => GameState::Game,
+                    Label::Back => GameState::Menu,
+                })
+                .unwrap(),
+            Interaction::Hovered => *color = theme.palette.hovered.into(),
+            Interaction::None => *color = theme.palette.button.into(),
+        });
+    }
+}
+
+impl CleanUp<Self> for GameSolved {}
+
+impl Plugin for GameSolved {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Solved).with_system(Self::enter))
+            .add_system_set(SystemSet::on_update(GameState::Solved).with_system(Self::update))
+            .add_system_set(SystemSet::on_exit(GameState::Solved).with_system(Self::exit));
+    }
+}
+
+// labels for each botton
+#[derive(Component, Clone, Copy)]
+enum Label {
+    Again,
+    Back,
+}
@@ -1,16 +1,15 @@
 use super::{CleanUp, GameState, TextLabel, TextScale};
-use crate::animation::AnimationEvent;
+use crate::{
+    animation::AnimationEvent,
+    theme::{Theme, Themed},
+};
 use bevy::prelude::*;
 
 #[derive(Component)]
 pub struct GameStart;
 
 impl GameStart {
-    fn enter(
-        mut commands: Commands,
-        mut writer: EventWriter<AnimationEvent>,
-        server: Res<AssetServer>,
-    ) {
+    fn enter(mut commands: Commands, mut writer: EventWriter<AnimationEvent>, theme: Res<Theme>) {
         writer.send(AnimationEvent);
         commands
             // whole window context
@@ -45,11 +44,7 @@ impl GameStart {
                             .spawn_bundle(TextBundle {
                                 text: Text::with_section(
                                     "Sliding Puzzle Game",
-                                    TextStyle {
-                                        color: Color::NAVY,
-                                        font: server.load("fonts/VictorMono-BoldItalic.ttf"),
-                                        ..Default::default()
-                                    },
+                                    theme.style(Themed::Text),
                                     TextAlignment {
                                         horizontal: HorizontalAlign::Center,
                                         vertical: VerticalAlign::Center,
@@ -57,7 +52,8 @@ impl GameStart {
                                 ),
                                 ..Default::default()
                             })
-                            .insert(TextLabel::with_section(TextScale::new(0.1, 0.2)));
+                            .insert(TextLabel::with_section(TextScale::new(0.1, 0.2)))
+                            .insert(Themed::Text);
                     });
                 parent
                     // 40 % bottom
@@ -83,35 +79,30 @@ impl GameStart {
                                 ..Default::default()
                             })
                             .insert(Label)
+                            .insert(Themed::Button)
                             .with_children(|parent| {
                                 parent
                                     .spawn_bundle(TextBundle {
-                                        text: Text::with_section(
-                                            "Start",
-                                            TextStyle {
-                                                color: Color::OLIVE,
-                                                font: server
-                                                    .load("fonts/VictorMono-BoldItalic.ttf"),
-                                                ..Default::default()
-                                            },
-                                            Default::default(),
-                                        ),
+                                        text: theme.text("Start", Themed::ButtonText),
                                         ..Default::default()
                                     })
-                                    .insert(TextLabel::with_section(TextScale::new(0.05, 0.1)));
+                                    .insert(TextLabel::with_section(TextScale::new(0.05, 0.1)))
+                                    .insert(Themed::ButtonText);
                             });
                     });
             });
     }
 
+    #[allow(clippy::type_complexity)]
     fn update(
+        theme: Res<Theme>,
         mut state: ResMut<State<GameState>>,
         mut query: Query<(&Interaction, &mut UiColor), (Changed<Interaction>, With<Label>)>,
     ) {
         query.for_each_mut(|(interaction, mut color)| match interaction {
             Interaction::Clicked => state.set(GameState::Menu).unwrap(),
-            Interaction::Hovered => *color = Color::GOLD.into(),
-            Interaction::None => *color = Color::YELLOW.into(),
+            Interaction::Hovered => *color = theme.palette.hovered.into(),
+            Interaction::None => *color = theme.palette.button.into(),
         });
     }
 }
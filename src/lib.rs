@@ -1,26 +1,56 @@
 mod animation;
+mod bindings;
+mod clipboard;
 mod floating;
+mod focus;
 mod game;
+mod game_bindings;
 mod game_menu;
+mod game_settings;
+mod game_solved;
 mod game_start;
+pub mod patterns;
+pub mod puzzle;
+mod settings;
+pub mod solver;
+mod storage;
+mod theme;
+mod ui;
 
 use animation::Animation;
 use bevy::{app::PluginGroupBuilder, prelude::*};
 use floating::Floating;
+use focus::Focus;
 use game::Game;
+use game_bindings::GameBindings;
 use game_menu::GameMenu;
+use game_settings::GameSettings;
+use game_solved::GameSolved;
 use game_start::GameStart;
+use patterns::Patterns;
+use puzzle::Puzzle;
+use settings::Preferences;
+use theme::Themes;
+
+pub use game::PictureMode;
 
 pub struct GamePlugins;
 
 impl PluginGroup for GamePlugins {
     fn build(&mut self, builder: &mut PluginGroupBuilder) {
         builder
+            .add(Preferences)
+            .add(Themes)
             .add(Animation)
             .add(Floating)
+            .add(Focus)
+            .add(Patterns)
             .add(GameStart)
             .add(GameMenu)
-            .add(Game);
+            .add(GameSettings)
+            .add(Game)
+            .add(GameSolved)
+            .add(GameBindings);
     }
 }
 
@@ -29,8 +59,12 @@ pub enum GameState {
     Start,
     Menu,
     Game,
+    Solved,
+    Settings,
+    Bindings,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for GameState {
     fn default() -> Self {
         Self::Start
@@ -62,8 +96,26 @@ impl TextScale {
     }
 }
 
-// 3x3, 4x4 or None
-struct GameMode(usize);
+// size of the board, from 2x2 to 10x10
+#[derive(Clone, Copy)]
+struct GameMode {
+    width: usize,
+    height: usize,
+}
+
+impl GameMode {
+    const MIN: usize = Puzzle::MIN;
+    const MAX: usize = Puzzle::MAX;
+}
+
+impl Default for GameMode {
+    fn default() -> Self {
+        Self {
+            width: 3,
+            height: 3,
+        }
+    }
+}
 
 trait CleanUp<T: Component> {
     // despawn all entity current state marked when exit
@@ -71,3 +123,9 @@ trait CleanUp<T: Component> {
         query.for_each(|entity| commands.entity(entity).despawn_recursive());
     }
 }
+
+// the option after this one, back to the first after the last
+fn cycle<T: Copy + PartialEq>(all: &[T], current: T) -> T {
+    let index = all.iter().position(|&item| item == current).unwrap();
+    all[(index + 1) % all.len()]
+}
@@ -2,26 +2,33 @@ use bevy::{
     input::system::exit_on_esc_system as exit_on_esc, prelude::*, render::camera::WindowOrigin,
     window::exit_on_window_close_system as exit_on_close,
 };
-use sliding_puzzle_game::{GamePlugins, GameState, TextLabel};
+use sliding_puzzle_game::{GamePlugins, GameState, PictureMode, TextLabel};
 
 #[bevy_main]
 fn main() {
-    App::new()
-        // background color
-        .insert_resource(ClearColor(Color::ORANGE))
-        .add_plugins(DefaultPlugins)
+    // a picture to play with at once, like `sliding_puzzle_game cat.jpg`
+    let picture = std::env::args().nth(1);
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins)
         // this is the actual game
         .add_plugins(GamePlugins)
-        // welcome to game
-        .add_state(GameState::default())
+        // welcome to game, or play with the picture
+        .add_state(match picture {
+            Some(_) => GameState::Game,
+            None => GameState::default(),
+        })
         // show our ui and so on
         .add_startup_system(setup_camera)
-        // window close don't app exit default
-        .add_system(exit_on_close)
+        // window close don't app exit default, exit before update so
+        // the game in progress is saved in the same frame
+        .add_system_to_stage(CoreStage::PreUpdate, exit_on_close)
         // exit on esc pressed
-        .add_system(exit_on_esc)
-        .add_system(viewport)
-        .run();
+        .add_system_to_stage(CoreStage::PreUpdate, exit_on_esc)
+        .add_system(viewport);
+    if let Some(path) = picture {
+        app.insert_resource(PictureMode(Some(path)));
+    }
+    app.run();
 }
 
 // setup both ui camera and 2d camera with origin is bottom left
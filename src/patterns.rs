@@ -0,0 +1,73 @@
+//! load the pattern databases shipped in `assets/patterns`, generated
+//! offline by the `patterns` tool, so that the solver can use them.
+use crate::solver::PatternDatabase;
+use bevy::{
+    asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+};
+use std::sync::Arc;
+
+pub struct Patterns;
+
+impl Patterns {
+    // boards which have a database in assets
+    const FILES: [&'static str; 2] = ["patterns/4x4.pdb", "patterns/5x5.pdb"];
+
+    fn setup(mut commands: Commands, server: Res<AssetServer>) {
+        let handles = Self::FILES.iter().map(|path| server.load(*path)).collect();
+        commands.insert_resource(PatternFiles(handles));
+    }
+}
+
+impl Plugin for Patterns {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<PatternFile>()
+            .init_asset_loader::<PatternLoader>()
+            .add_startup_system(Self::setup);
+    }
+}
+
+#[derive(TypeUuid)]
+#[uuid = "5c1a2f0e-8d3b-4b6e-9f47-2a6c1e93d7b4"]
+pub struct PatternFile(pub Arc<PatternDatabase>);
+
+// handles of all shipped databases, loaded in background
+pub struct PatternFiles(Vec<Handle<PatternFile>>);
+
+impl PatternFiles {
+    // the database of the board size if it is loaded
+    pub fn find(
+        &self,
+        assets: &Assets<PatternFile>,
+        width: usize,
+        height: usize,
+    ) -> Option<Arc<PatternDatabase>> {
+        self.0
+            .iter()
+            .filter_map(|handle| assets.get(handle))
+            .find(|file| file.0.width() == width && file.0.height() == height)
+            .map(|file| file.0.clone())
+    }
+}
+
+#[derive(Default)]
+struct PatternLoader;
+
+impl AssetLoader for PatternLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let database = PatternDatabase::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(PatternFile(Arc::new(database))));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["pdb"]
+    }
+}
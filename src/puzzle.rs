@@ -0,0 +1,799 @@
+//! the rules of the sliding puzzle, free of any game engine so that
+//! tools and bots can share them with the game.
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, error::Error, fmt, str::FromStr};
+
+// a board of width x height cells, the blank is numbered 0
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Puzzle {
+    width: usize,
+    height: usize,
+    // number on each cell, from left to right and top to bottom
+    tiles: Vec<usize>,
+    // the cell of blank
+    blank: usize,
+}
+
+impl Puzzle {
+    // sides of boards which can be played, shared or saved
+    pub const MIN: usize = 2;
+    pub const MAX: usize = 10;
+
+    // create a solved puzzle, numbers from 1 and the blank at the last
+    pub fn new(width: usize, height: usize) -> Self {
+        assert!(width > 1 && height > 1, "a puzzle is at least 2x2");
+        let size = width * height;
+        Self {
+            width,
+            height,
+            tiles: (1..size).chain([0]).collect(),
+            blank: size - 1,
+        }
+    }
+
+    // a puzzle of the numbers from left to right and top to bottom, each
+    // of 0 to size - 1 must be there once and it must be solvable
+    pub fn from_tiles(width: usize, height: usize, tiles: Vec<usize>) -> Result<Self, ParseError> {
+        Self::check_size(width, height)?;
+        let size = tiles.len();
+        if width.checked_mul(height) != Some(size) {
+            return Err(ParseError::TileCount {
+                width,
+                height,
+                found: size,
+            });
+        }
+        let mut seen = vec![false; size];
+        tiles
+            .iter()
+            .try_for_each(|&tile| match seen.get_mut(tile) {
+                None => Err(ParseError::OutOfRange { tile, size }),
+                Some(true) => Err(ParseError::Duplicate(tile)),
+                Some(seen) => {
+                    *seen = true;
+                    Ok(())
+                }
+            })?;
+        let blank = tiles.iter().position(|&tile| tile == 0).unwrap();
+        let puzzle = Self {
+            width,
+            height,
+            tiles,
+            blank,
+        };
+        match puzzle.is_solvable() {
+            true => Ok(puzzle),
+            false => Err(ParseError::Unsolvable),
+        }
+    }
+
+    fn check_size(width: usize, height: usize) -> Result<(), ParseError> {
+        if width < Self::MIN || height < Self::MIN {
+            return Err(ParseError::TooSmall { width, height });
+        }
+        if width > Self::MAX || height > Self::MAX {
+            return Err(ParseError::TooLarge { width, height });
+        }
+        Ok(())
+    }
+
+    // the board in one line like 3x3:1,2,3/4,5,6/7,8,0 to be shared as
+    // text, rows from top to bottom and the blank is 0
+    pub fn notation(&self) -> String {
+        let rows = self
+            .tiles
+            .chunks(self.width)
+            .map(|row| {
+                row.iter()
+                    .map(usize::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>();
+        format!("{}x{}:{}", self.width, self.height, rows.join("/"))
+    }
+
+    // shuffle a puzzle by the given seed, the same seed always gives
+    // the same puzzle, the result is solvable and never solved
+    pub fn shuffled(width: usize, height: usize, seed: u64) -> Self {
+        let mut puzzle = Self::new(width, height);
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        while puzzle.is_solved() {
+            puzzle.tiles.shuffle(&mut rng);
+            puzzle.blank = puzzle.tiles.iter().position(|&tile| tile == 0).unwrap();
+            if !puzzle.is_solvable() {
+                // swap two numbers (not the blank) to flip the parity
+                let size = puzzle.tiles.len();
+                match puzzle.blank {
+                    0 | 1 => puzzle.tiles.swap(size - 1, size - 2),
+                    _ => puzzle.tiles.swap(0, 1),
+                }
+            }
+        }
+        puzzle
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // numbers from left to right and top to bottom
+    pub fn tiles(&self) -> &[usize] {
+        &self.tiles
+    }
+
+    pub fn tile_at(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| self.tiles[y * self.width + x])
+    }
+
+    // where is the number, panic if out of board
+    pub fn position(&self, tile: usize) -> (usize, usize) {
+        let index = self.tiles.iter().position(|&num| num == tile).unwrap();
+        (index % self.width, index / self.width)
+    }
+
+    // where is the number once solved, the last cell for blank
+    pub fn goal(&self, tile: usize) -> (usize, usize) {
+        let index = (tile + self.tiles.len() - 1) % self.tiles.len();
+        (index % self.width, index / self.width)
+    }
+
+    pub fn blank(&self) -> (usize, usize) {
+        (self.blank % self.width, self.blank / self.width)
+    }
+
+    // moves which have a number to slide into the blank
+    pub fn legal_moves(&self) -> Vec<Move> {
+        Move::ALL
+            .into_iter()
+            .filter(|&mv| self.source(mv).is_some())
+            .collect()
+    }
+
+    // slide a number into the blank, return the moved number
+    pub fn apply(&mut self, mv: Move) -> Result<usize, MoveError> {
+        let source = self.source(mv).ok_or(MoveError::Blocked(mv))?;
+        let target = self.blank;
+        self.tiles.swap(source, target);
+        self.blank = source;
+        Ok(self.tiles[target])
+    }
+
+    // the move which slides the number into the blank
+    pub fn move_of(&self, tile: usize) -> Result<Move, MoveError> {
+        if tile == 0 || tile >= self.tiles.len() {
+            return Err(MoveError::NoSuchTile(tile));
+        }
+        Move::ALL
+            .into_iter()
+            .find(|&mv| self.source(mv).map(|cell| self.tiles[cell]) == Some(tile))
+            .ok_or(MoveError::NotMovable(tile))
+    }
+
+    // the move and how many numbers slide together if the number is in
+    // the same row or column as the blank
+    pub fn line_of(&self, tile: usize) -> Result<(Move, usize), MoveError> {
+        if tile == 0 || tile >= self.tiles.len() {
+            return Err(MoveError::NoSuchTile(tile));
+        }
+        let (x, y) = self.position(tile);
+        let (bx, by) = self.blank();
+        match (x.cmp(&bx), y.cmp(&by)) {
+            (Ordering::Less, Ordering::Equal) => Ok((Move::Right, bx - x)),
+            (Ordering::Greater, Ordering::Equal) => Ok((Move::Left, x - bx)),
+            (Ordering::Equal, Ordering::Less) => Ok((Move::Down, by - y)),
+            (Ordering::Equal, Ordering::Greater) => Ok((Move::Up, y - by)),
+            _ => Err(MoveError::NotMovable(tile)),
+        }
+    }
+
+    // slide some numbers in a line at once, nothing moves if any is blocked
+    pub fn slide(&mut self, mv: Move, count: usize) -> Result<(), MoveError> {
+        let (x, y) = self.blank();
+        let room = match mv {
+            Move::Up => self.height - 1 - y,
+            Move::Down => y,
+            Move::Left => self.width - 1 - x,
+            Move::Right => x,
+        };
+        if count > room {
+            return Err(MoveError::Blocked(mv));
+        }
+        (0..count).for_each(|_| {
+            self.apply(mv).unwrap();
+        });
+        Ok(())
+    }
+
+    // each number n is at the (n - 1)th cell, and the blank is the last
+    pub fn is_solved(&self) -> bool {
+        let size = self.tiles.len();
+        self.tiles
+            .iter()
+            .enumerate()
+            .all(|(index, &tile)| (index + 1) % size == tile)
+    }
+
+    // a puzzle can be solved only if the parity of the permutation equals
+    // the parity of the blank's taxicab distance from its goal cell, this
+    // is the same as the classic inversion rule (including the blank row
+    // rule when the width is even)
+    pub fn is_solvable(&self) -> bool {
+        let size = self.tiles.len();
+        // goal cell of number n is n - 1 (or the last for blank)
+        let target = |tile: usize| (tile + size - 1) % size;
+        let mut visited = vec![false; size];
+        let mut transpositions = 0;
+        (0..size).for_each(|start| {
+            let mut index = start;
+            let mut cycle = 0;
+            while !visited[index] {
+                visited[index] = true;
+                index = target(self.tiles[index]);
+                cycle += 1;
+            }
+            transpositions += cycle.max(1) - 1;
+        });
+        let (x, y) = self.blank();
+        let distance = (self.width - 1 - x) + (self.height - 1 - y);
+        transpositions % 2 == distance % 2
+    }
+
+    // the cell of number which would slide into the blank
+    fn source(&self, mv: Move) -> Option<usize> {
+        let (x, y) = self.blank();
+        match mv {
+            Move::Up => (y + 1 < self.height).then(|| self.blank + self.width),
+            Move::Down => (y > 0).then(|| self.blank - self.width),
+            Move::Left => (x + 1 < self.width).then(|| self.blank + 1),
+            Move::Right => (x > 0).then(|| self.blank - 1),
+        }
+    }
+}
+
+impl fmt::Display for Puzzle {
+    // numbers aligned in rows, the blank is a dot
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.tiles.len().to_string().len();
+        self.tiles.chunks(self.width).try_for_each(|row| {
+            let row = row
+                .iter()
+                .map(|&tile| match tile {
+                    0 => format!("{:>width$}", "."),
+                    _ => format!("{:>width$}", tile),
+                })
+                .collect::<Vec<_>>();
+            writeln!(f, "{}", row.join(" "))
+        })
+    }
+}
+
+// read the notation, spaces around numbers are allowed
+impl FromStr for Puzzle {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (size, rows) = text.trim().split_once(':').ok_or(ParseError::NoSize)?;
+        let bad_size = || ParseError::BadSize(size.trim().to_string());
+        let (width, height) = size.trim().split_once(['x', 'X']).ok_or_else(bad_size)?;
+        let (width, height) = match (width.trim().parse(), height.trim().parse()) {
+            (Ok(width), Ok(height)) => (width, height),
+            _ => return Err(bad_size()),
+        };
+        // checked before allocating the numbers
+        Self::check_size(width, height)?;
+        let rows = rows.split('/').collect::<Vec<_>>();
+        if rows.len() != height {
+            return Err(ParseError::RowCount {
+                expected: height,
+                found: rows.len(),
+            });
+        }
+        let mut tiles = Vec::with_capacity(rows.len() * width);
+        rows.into_iter().enumerate().try_for_each(|(index, row)| {
+            let row = row
+                .split(',')
+                .map(|tile| {
+                    let tile = tile.trim();
+                    tile.parse::<usize>()
+                        .map_err(|_| ParseError::BadNumber(tile.to_string()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if row.len() != width {
+                return Err(ParseError::RowLength {
+                    row: index + 1,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            tiles.extend(row);
+            Ok(())
+        })?;
+        Self::from_tiles(width, height, tiles)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    // no size like 3x3: before the rows
+    NoSize,
+    // the size is not two numbers joined by x
+    BadSize(String),
+    TooSmall {
+        width: usize,
+        height: usize,
+    },
+    TooLarge {
+        width: usize,
+        height: usize,
+    },
+    // not the same number of rows as the height
+    RowCount {
+        expected: usize,
+        found: usize,
+    },
+    // not the same number of numbers as the width, rows are from 1
+    RowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    TileCount {
+        width: usize,
+        height: usize,
+        found: usize,
+    },
+    // something in a list of numbers is not a number
+    BadNumber(String),
+    // a number not less than the size of board
+    OutOfRange {
+        tile: usize,
+        size: usize,
+    },
+    Duplicate(usize),
+    Unsolvable,
+    // a letter of moves which is not one of U, D, L and R, counted from 1
+    BadMove {
+        letter: char,
+        position: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoSize => f.write_str("missing size like 3x3: before the rows"),
+            Self::BadSize(size) => write!(f, "size {:?} is not like 3x3", size),
+            Self::TooSmall { width, height } => {
+                write!(f, "a {}x{} board is smaller than 2x2", width, height)
+            }
+            Self::TooLarge { width, height } => {
+                write!(f, "a {}x{} board is larger than 10x10", width, height)
+            }
+            Self::RowCount { expected, found } => {
+                write!(f, "expected {} rows but found {}", expected, found)
+            }
+            Self::RowLength {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} numbers instead of {}",
+                row, found, expected
+            ),
+            Self::TileCount {
+                width,
+                height,
+                found,
+            } => write!(
+                f,
+                "a {}x{} board can not have {} numbers",
+                width, height, found
+            ),
+            Self::BadNumber(tile) => write!(f, "{:?} is not a number", tile),
+            Self::OutOfRange { tile, size } => {
+                write!(f, "number {} is not less than {}", tile, size)
+            }
+            Self::Duplicate(tile) => write!(f, "number {} appears more than once", tile),
+            Self::Unsolvable => f.write_str("the board can not be solved"),
+            Self::BadMove { letter, position } => {
+                write!(
+                    f,
+                    "move {:?} at {} is not one of U, D, L and R",
+                    letter, position
+                )
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// the direction which a number slides to (into the blank)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Move {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Move {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    pub fn letter(self) -> char {
+        match self {
+            Self::Up => 'U',
+            Self::Down => 'D',
+            Self::Left => 'L',
+            Self::Right => 'R',
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<Self> {
+        match letter.to_ascii_uppercase() {
+            'U' => Some(Self::Up),
+            'D' => Some(Self::Down),
+            'L' => Some(Self::Left),
+            'R' => Some(Self::Right),
+            _ => None,
+        }
+    }
+
+    // the move which undoes this one
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+// moves as letters like UDLR, the direction which numbers slide to
+pub fn format_moves(moves: &[Move]) -> String {
+    moves.iter().map(|mv| mv.letter()).collect()
+}
+
+// read moves from letters, in either case and spaces are skipped
+pub fn parse_moves(text: &str) -> Result<Vec<Move>, ParseError> {
+    text.chars()
+        .enumerate()
+        .filter(|(_, letter)| !letter.is_whitespace())
+        .map(|(index, letter)| {
+            Move::from_letter(letter).ok_or(ParseError::BadMove {
+                letter,
+                position: index + 1,
+            })
+        })
+        .collect()
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Left => "left",
+            Self::Right => "right",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveError {
+    // no number on that side of the blank
+    Blocked(Move),
+    // the number is not next to the blank
+    NotMovable(usize),
+    // the number is not on the board
+    NoSuchTile(usize),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Blocked(mv) => write!(f, "no number can slide {}", mv),
+            Self::NotMovable(tile) => write!(f, "number {} is not next to the blank", tile),
+            Self::NoSuchTile(tile) => write!(f, "number {} is not on the board", tile),
+        }
+    }
+}
+
+impl Error for MoveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the classic rule, inversions of numbers plus the blank row from
+    // bottom when the width is even
+    fn by_inversions(puzzle: &Puzzle) -> bool {
+        let tiles = puzzle
+            .tiles
+            .iter()
+            .copied()
+            .filter(|&tile| tile != 0)
+            .collect::<Vec<_>>();
+        let inversions = (0..tiles.len())
+            .flat_map(|i| (i + 1..tiles.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| tiles[i] > tiles[j])
+            .count();
+        let (_, y) = puzzle.blank();
+        match puzzle.width % 2 {
+            0 => (inversions + puzzle.height - 1 - y) % 2 == 0,
+            _ => inversions % 2 == 0,
+        }
+    }
+
+    fn swapped(width: usize, height: usize, a: usize, b: usize) -> Puzzle {
+        let mut puzzle = Puzzle::new(width, height);
+        puzzle.tiles.swap(a, b);
+        puzzle
+    }
+
+    #[test]
+    fn apply_slides_into_blank() {
+        let mut puzzle = Puzzle::new(3, 3);
+        assert_eq!(puzzle.apply(Move::Down), Ok(6));
+        assert_eq!(puzzle.blank(), (2, 1));
+        assert_eq!(puzzle.tile_at(2, 2), Some(6));
+        assert_eq!(puzzle.apply(Move::Right), Ok(5));
+        assert_eq!(puzzle.blank(), (1, 1));
+        assert_eq!(puzzle.apply(Move::Left), Ok(5));
+        assert_eq!(puzzle.apply(Move::Up), Ok(6));
+        assert!(puzzle.is_solved());
+    }
+
+    #[test]
+    fn apply_blocked() {
+        let mut puzzle = Puzzle::new(3, 3);
+        assert_eq!(puzzle.apply(Move::Up), Err(MoveError::Blocked(Move::Up)));
+        assert_eq!(
+            puzzle.apply(Move::Left),
+            Err(MoveError::Blocked(Move::Left))
+        );
+        assert_eq!(puzzle, Puzzle::new(3, 3));
+    }
+
+    #[test]
+    fn move_errors() {
+        let puzzle = Puzzle::new(3, 3);
+        assert_eq!(puzzle.move_of(6), Ok(Move::Down));
+        assert_eq!(puzzle.move_of(8), Ok(Move::Right));
+        assert_eq!(puzzle.move_of(0), Err(MoveError::NoSuchTile(0)));
+        assert_eq!(puzzle.move_of(9), Err(MoveError::NoSuchTile(9)));
+        assert_eq!(puzzle.move_of(1), Err(MoveError::NotMovable(1)));
+    }
+
+    #[test]
+    fn slide_lines() {
+        let mut puzzle = Puzzle::new(3, 3);
+        assert_eq!(puzzle.line_of(7), Ok((Move::Right, 2)));
+        assert_eq!(puzzle.line_of(3), Ok((Move::Down, 2)));
+        assert_eq!(puzzle.line_of(5), Err(MoveError::NotMovable(5)));
+        assert_eq!(
+            puzzle.slide(Move::Right, 3),
+            Err(MoveError::Blocked(Move::Right))
+        );
+        assert_eq!(puzzle, Puzzle::new(3, 3));
+        assert_eq!(puzzle.slide(Move::Right, 2), Ok(()));
+        assert_eq!(puzzle.blank(), (0, 2));
+    }
+
+    #[test]
+    fn legal_moves() {
+        let mut puzzle = Puzzle::new(3, 3);
+        assert_eq!(puzzle.legal_moves(), [Move::Down, Move::Right]);
+        puzzle.apply(Move::Down).unwrap();
+        puzzle.apply(Move::Right).unwrap();
+        assert_eq!(puzzle.legal_moves(), Move::ALL);
+        puzzle.apply(Move::Down).unwrap();
+        puzzle.apply(Move::Right).unwrap();
+        assert_eq!(puzzle.blank(), (0, 0));
+        assert_eq!(puzzle.legal_moves(), [Move::Up, Move::Left]);
+    }
+
+    #[test]
+    fn swapped_numbers_are_unsolvable() {
+        [(3, 3), (4, 4), (2, 3), (3, 2), (4, 2), (5, 3)]
+            .into_iter()
+            .for_each(|(width, height)| {
+                assert!(Puzzle::new(width, height).is_solvable());
+                assert!(!swapped(width, height, 0, 1).is_solvable());
+                // two swaps are even again
+                let mut puzzle = swapped(width, height, 0, 1);
+                puzzle.tiles.swap(1, 2);
+                assert!(puzzle.is_solvable());
+            });
+    }
+
+    #[test]
+    fn blank_row_matters_on_even_width() {
+        // numbers in order and the blank a row up at the end of it
+        let up = |width: usize, height: usize| {
+            let size = width * height;
+            let blank = size - 1 - width;
+            let tiles = (1..size)
+                .take(blank)
+                .chain([0])
+                .chain(blank + 1..size)
+                .collect();
+            Puzzle {
+                width,
+                height,
+                tiles,
+                blank,
+            }
+        };
+        assert!(up(3, 3).is_solvable());
+        assert!(up(3, 2).is_solvable());
+        assert!(!up(4, 4).is_solvable());
+        assert!(!up(4, 3).is_solvable());
+        assert!(!up(2, 3).is_solvable());
+    }
+
+    #[test]
+    fn solvable_same_as_inversions() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        [
+            (2, 2),
+            (3, 3),
+            (4, 4),
+            (5, 5),
+            (2, 4),
+            (4, 2),
+            (3, 5),
+            (6, 3),
+        ]
+        .into_iter()
+        .for_each(|(width, height)| {
+            (0..100).for_each(|_| {
+                let mut puzzle = Puzzle::new(width, height);
+                puzzle.tiles.shuffle(&mut rng);
+                puzzle.blank = puzzle.tiles.iter().position(|&tile| tile == 0).unwrap();
+                assert_eq!(
+                    puzzle.is_solvable(),
+                    by_inversions(&puzzle),
+                    "{}x{}\n{}",
+                    width,
+                    height,
+                    puzzle
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn shuffled_is_solvable_and_not_solved() {
+        [(2, 2), (3, 3), (4, 4), (2, 5), (5, 2), (10, 10)]
+            .into_iter()
+            .for_each(|(width, height)| {
+                (0..50).for_each(|seed| {
+                    let puzzle = Puzzle::shuffled(width, height, seed);
+                    assert!(puzzle.is_solvable());
+                    assert!(!puzzle.is_solved());
+                    assert_eq!(puzzle, Puzzle::shuffled(width, height, seed));
+                });
+            });
+    }
+
+    #[test]
+    fn notation_round_trip() {
+        [(2, 2), (3, 3), (4, 4), (3, 5), (10, 10)]
+            .into_iter()
+            .for_each(|(width, height)| {
+                (0..10).for_each(|seed| {
+                    let puzzle = Puzzle::shuffled(width, height, seed);
+                    assert_eq!(puzzle.notation().parse(), Ok(puzzle.clone()));
+                    assert_eq!(puzzle.to_string().lines().count(), height);
+                });
+            });
+        let puzzle = " 3X2 : 1, 2 ,3 / 4 , 0,5 ".parse::<Puzzle>().unwrap();
+        assert_eq!(puzzle.notation(), "3x2:1,2,3/4,0,5");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let parse = |text: &str| text.parse::<Puzzle>().unwrap_err();
+        assert_eq!(parse("1,2/3,0"), ParseError::NoSize);
+        assert_eq!(parse("2by2:1,2/3,0"), ParseError::BadSize("2by2".into()));
+        assert_eq!(parse("2x:1,2/3,0"), ParseError::BadSize("2x".into()));
+        assert_eq!(
+            parse("1x2:1/0"),
+            ParseError::TooSmall {
+                width: 1,
+                height: 2
+            }
+        );
+        assert_eq!(
+            parse("11x2:1/0"),
+            ParseError::TooLarge {
+                width: 11,
+                height: 2
+            }
+        );
+        assert_eq!(
+            parse("4000000000x2:1/2"),
+            ParseError::TooLarge {
+                width: 4_000_000_000,
+                height: 2
+            }
+        );
+        assert_eq!(
+            parse("2x2:1,2"),
+            ParseError::RowCount {
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(
+            parse("2x2:1,2/3"),
+            ParseError::RowLength {
+                row: 2,
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(parse("2x2:1,a/3,0"), ParseError::BadNumber("a".into()));
+        assert_eq!(parse("2x2:1,2/3,-1"), ParseError::BadNumber("-1".into()));
+        assert_eq!(
+            parse("2x2:1,2/4,0"),
+            ParseError::OutOfRange { tile: 4, size: 4 }
+        );
+        assert_eq!(parse("2x2:1,1/3,0"), ParseError::Duplicate(1));
+        assert_eq!(parse("2x2:2,1/3,0"), ParseError::Unsolvable);
+        assert_eq!(
+            Puzzle::from_tiles(2, 2, vec![1, 2, 0]),
+            Err(ParseError::TileCount {
+                width: 2,
+                height: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Puzzle::from_tiles(11, 11, (0..121).collect()),
+            Err(ParseError::TooLarge {
+                width: 11,
+                height: 11
+            })
+        );
+        assert_eq!(
+            parse_moves("UDxL"),
+            Err(ParseError::BadMove {
+                letter: 'x',
+                position: 3
+            })
+        );
+    }
+
+    #[test]
+    fn display() {
+        let mut puzzle = Puzzle::new(4, 3);
+        puzzle.apply(Move::Down).unwrap();
+        assert_eq!(
+            puzzle.to_string(),
+            " 1  2  3  4\n 5  6  7  .\n 9 10 11  8\n"
+        );
+        assert_eq!(Puzzle::new(2, 2).to_string(), "1 2\n3 .\n");
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        let moves = [Move::Up, Move::Down, Move::Left, Move::Right, Move::Up];
+        assert_eq!(format_moves(&moves), "UDLRU");
+        assert_eq!(parse_moves("UDLRU"), Ok(moves.to_vec()));
+        assert_eq!(parse_moves(" ud lr U "), Ok(moves.to_vec()));
+        moves
+            .into_iter()
+            .for_each(|mv| assert_eq!(mv.opposite().opposite(), mv));
+    }
+}
@@ -0,0 +1,182 @@
+//! choices of the player, loaded at startup and saved once changed, each
+//! of them is copied from and to the resource which uses it.
+use super::GameMode;
+use crate::{
+    game::{
+        CountUndo, Easing, MoveMetric, PictureMode, PlaybackSpeed, ShowNumbers, SlideAnimation,
+        TileColoring,
+    },
+    storage,
+    theme::ThemeName,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::Duration};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+pub struct Preferences;
+
+// the resources the settings are copied from
+#[derive(SystemParam)]
+struct Choices<'w, 's> {
+    mode: Res<'w, GameMode>,
+    theme: Res<'w, ThemeName>,
+    animation: Res<'w, SlideAnimation>,
+    metric: Res<'w, MoveMetric>,
+    numbers: Res<'w, ShowNumbers>,
+    coloring: Res<'w, TileColoring>,
+    picture: Res<'w, PictureMode>,
+    count_undo: Res<'w, CountUndo>,
+    speed: Res<'w, PlaybackSpeed>,
+    volume: Res<'w, Volume>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+impl Preferences {
+    // keep the settings same as the resources
+    fn collect(choices: Choices, mut settings: ResMut<Settings>) {
+        let Choices {
+            mode,
+            theme,
+            animation,
+            metric,
+            numbers,
+            coloring,
+            picture,
+            count_undo,
+            speed,
+            volume,
+            ..
+        } = choices;
+        let collected = Settings {
+            version: Settings::VERSION,
+            width: mode.width,
+            height: mode.height,
+            theme: *theme,
+            easing: animation.easing,
+            slide_millis: animation.duration.as_millis() as u64,
+            metric: *metric,
+            show_numbers: numbers.0,
+            coloring: *coloring,
+            picture: picture.clone(),
+            count_undo: count_undo.0,
+            playback_millis: speed.0.as_millis() as u64,
+            volume: volume.0,
+        };
+        // only changed if different
+        if *settings != collected {
+            *settings = collected;
+        }
+    }
+
+    fn save(settings: Res<Settings>) {
+        if settings.is_changed() && !settings.is_added() {
+            settings.save();
+        }
+    }
+}
+
+impl Plugin for Preferences {
+    fn build(&self, app: &mut App) {
+        let settings = Settings::load();
+        app.insert_resource(GameMode {
+            width: settings.width.clamp(GameMode::MIN, GameMode::MAX),
+            height: settings.height.clamp(GameMode::MIN, GameMode::MAX),
+        })
+        .insert_resource(settings.theme)
+        .insert_resource(SlideAnimation {
+            duration: Duration::from_millis(settings.slide_millis),
+            easing: settings.easing,
+        })
+        .insert_resource(settings.metric)
+        .insert_resource(ShowNumbers(settings.show_numbers))
+        .insert_resource(settings.coloring)
+        .insert_resource(settings.picture.clone())
+        .insert_resource(CountUndo(settings.count_undo))
+        .insert_resource(PlaybackSpeed(Duration::from_millis(
+            settings.playback_millis,
+        )))
+        .insert_resource(Volume(settings.volume.min(Volume::MAX)))
+        .insert_resource(settings)
+        .add_system_to_stage(CoreStage::PostUpdate, Self::collect)
+        .add_system_to_stage(CoreStage::Last, Self::save);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    // the schema of file, missing fields are filled by default
+    pub version: u32,
+    pub width: usize,
+    pub height: usize,
+    pub theme: ThemeName,
+    pub easing: Easing,
+    pub slide_millis: u64,
+    pub metric: MoveMetric,
+    pub show_numbers: bool,
+    pub coloring: TileColoring,
+    pub picture: PictureMode,
+    pub count_undo: bool,
+    pub playback_millis: u64,
+    pub volume: u8,
+}
+
+impl Settings {
+    const KEY: &'static str = "settings.ron";
+    const VERSION: u32 = 1;
+
+    // the saved settings, or defaults if missing, broken or too new
+    pub fn load() -> Self {
+        match storage::load::<Self>(Self::KEY) {
+            Some(settings) if settings.version <= Self::VERSION => Self {
+                version: Self::VERSION,
+                ..settings
+            },
+            Some(settings) => {
+                warn!("ignore settings of newer version {}", settings.version);
+                Self::default()
+            }
+            None => Self::default(),
+        }
+    }
+
+    pub fn save(&self) {
+        storage::save(Self::KEY, self);
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        let mode = GameMode::default();
+        let animation = SlideAnimation::default();
+        Self {
+            version: Self::VERSION,
+            width: mode.width,
+            height: mode.height,
+            theme: ThemeName::default(),
+            easing: animation.easing,
+            slide_millis: animation.duration.as_millis() as u64,
+            metric: MoveMetric::default(),
+            show_numbers: ShowNumbers::default().0,
+            coloring: TileColoring::default(),
+            picture: PictureMode::default(),
+            count_undo: CountUndo::default().0,
+            playback_millis: PlaybackSpeed::default().0.as_millis() as u64,
+            volume: Volume::default().0,
+        }
+    }
+}
+
+// volume of sounds in percent, kept for when the game has any
+pub struct Volume(pub u8);
+
+impl Volume {
+    pub const MAX: u8 = 100;
+}
+
+impl Default for Volume {
+    fn default() -> Self {
+        Self(80)
+    }
+}
@@ -0,0 +1,177 @@
+use super::SolveError;
+use std::sync::Arc;
+
+// estimate how many moves are needed at least, never overestimate
+pub trait Heuristic {
+    // whatever kept to update the estimate incrementally
+    type Estimate: Copy;
+
+    // estimate a whole board, numbers from left to right and top to bottom
+    fn estimate(&self, tiles: &[u8]) -> Self::Estimate;
+
+    // estimate again after the number at `to` has just been moved from `from`
+    fn update(
+        &self,
+        tiles: &[u8],
+        estimate: Self::Estimate,
+        from: usize,
+        to: usize,
+    ) -> Self::Estimate;
+
+    // the fewest moves needed by the estimate
+    fn moves(&self, estimate: Self::Estimate) -> u32;
+}
+
+impl<H: Heuristic + ?Sized> Heuristic for &H {
+    type Estimate = H::Estimate;
+
+    fn estimate(&self, tiles: &[u8]) -> Self::Estimate {
+        (**self).estimate(tiles)
+    }
+
+    fn update(
+        &self,
+        tiles: &[u8],
+        estimate: Self::Estimate,
+        from: usize,
+        to: usize,
+    ) -> Self::Estimate {
+        (**self).update(tiles, estimate, from, to)
+    }
+
+    fn moves(&self, estimate: Self::Estimate) -> u32 {
+        (**self).moves(estimate)
+    }
+}
+
+// shared with a solver in background
+impl<H: Heuristic + ?Sized> Heuristic for Arc<H> {
+    type Estimate = H::Estimate;
+
+    fn estimate(&self, tiles: &[u8]) -> Self::Estimate {
+        (**self).estimate(tiles)
+    }
+
+    fn update(
+        &self,
+        tiles: &[u8],
+        estimate: Self::Estimate,
+        from: usize,
+        to: usize,
+    ) -> Self::Estimate {
+        (**self).update(tiles, estimate, from, to)
+    }
+
+    fn moves(&self, estimate: Self::Estimate) -> u32 {
+        (**self).moves(estimate)
+    }
+}
+
+// manhattan distance plus two moves for each linear conflict
+pub struct LinearConflict {
+    width: usize,
+    height: usize,
+}
+
+impl LinearConflict {
+    // lines are collected on stack
+    const MAX: usize = 32;
+
+    pub fn new(width: usize, height: usize) -> Result<Self, SolveError> {
+        match width <= Self::MAX && height <= Self::MAX {
+            true => Ok(Self { width, height }),
+            false => Err(SolveError::TooLarge),
+        }
+    }
+
+    // goal cell of number n is n - 1
+    fn goal(&self, tile: u8) -> (usize, usize) {
+        let index = tile as usize - 1;
+        (index % self.width, index / self.width)
+    }
+
+    fn distance(&self, tile: u8, cell: usize) -> u32 {
+        let (x, y) = self.goal(tile);
+        let (cx, cy) = (cell % self.width, cell / self.width);
+        (x.abs_diff(cx) + y.abs_diff(cy)) as u32
+    }
+
+    // conflicts in a row, given the number on each cell
+    fn row(&self, y: usize, tile_at: impl Fn(usize) -> u8) -> u32 {
+        Self::conflicts((0..self.width).filter_map(|x| {
+            let tile = tile_at(y * self.width + x);
+            let goal = (tile != 0).then(|| self.goal(tile));
+            goal.filter(|goal| goal.1 == y).map(|goal| goal.0)
+        }))
+    }
+
+    // conflicts in a column, given the number on each cell
+    fn column(&self, x: usize, tile_at: impl Fn(usize) -> u8) -> u32 {
+        Self::conflicts((0..self.height).filter_map(|y| {
+            let tile = tile_at(y * self.width + x);
+            let goal = (tile != 0).then(|| self.goal(tile));
+            goal.filter(|goal| goal.0 == x).map(|goal| goal.1)
+        }))
+    }
+
+    // numbers which have to leave the line so that the rest are in
+    // order, the line length minus the longest increasing subsequence
+    fn conflicts(goals: impl Iterator<Item = usize>) -> u32 {
+        let mut tails = [0; Self::MAX];
+        let (mut count, mut longest) = (0, 0);
+        goals.for_each(|goal| {
+            let index = tails[..longest].partition_point(|&tail| tail < goal);
+            tails[index] = goal;
+            longest = longest.max(index + 1);
+            count += 1;
+        });
+        (count - longest) as u32
+    }
+}
+
+impl Heuristic for LinearConflict {
+    type Estimate = u32;
+
+    fn estimate(&self, tiles: &[u8]) -> u32 {
+        let tile_at = |cell: usize| tiles[cell];
+        let distance: u32 = tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, &tile)| tile != 0)
+            .map(|(cell, &tile)| self.distance(tile, cell))
+            .sum();
+        let rows: u32 = (0..self.height).map(|y| self.row(y, tile_at)).sum();
+        let columns: u32 = (0..self.width).map(|x| self.column(x, tile_at)).sum();
+        distance + 2 * (rows + columns)
+    }
+
+    fn update(&self, tiles: &[u8], estimate: u32, from: usize, to: usize) -> u32 {
+        let tile = tiles[to];
+        let after = |cell: usize| tiles[cell];
+        let before = |cell: usize| match cell {
+            _ if cell == from => tile,
+            _ if cell == to => 0,
+            _ => tiles[cell],
+        };
+        let (old, new) = if from / self.width == to / self.width {
+            // moved in a row, only two columns changed
+            let columns = [from % self.width, to % self.width];
+            (
+                columns.iter().map(|&x| self.column(x, before)).sum::<u32>(),
+                columns.iter().map(|&x| self.column(x, after)).sum::<u32>(),
+            )
+        } else {
+            // moved in a column, only two rows changed
+            let rows = [from / self.width, to / self.width];
+            (
+                rows.iter().map(|&y| self.row(y, before)).sum::<u32>(),
+                rows.iter().map(|&y| self.row(y, after)).sum::<u32>(),
+            )
+        };
+        estimate + self.distance(tile, to) + 2 * new - self.distance(tile, from) - 2 * old
+    }
+
+    fn moves(&self, estimate: u32) -> u32 {
+        estimate
+    }
+}
@@ -0,0 +1,362 @@
+//! find the shortest solution of a puzzle by IDA*, cancellable so that
+//! it can run in the background of the game.
+mod heuristic;
+mod pattern;
+
+pub use heuristic::{Heuristic, LinearConflict};
+pub use pattern::{PatternDatabase, PatternError};
+
+use crate::puzzle::{Move, Puzzle};
+use instant::{Duration, Instant};
+use std::{
+    error::Error,
+    fmt, mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+// solve by manhattan distance and linear conflicts
+pub fn solve(puzzle: &Puzzle, budget: &Budget) -> Result<Vec<Move>, SolveError> {
+    let heuristic = LinearConflict::new(puzzle.width(), puzzle.height())?;
+    solve_with(puzzle, &heuristic, budget)
+}
+
+// solve by any heuristic, the solution is the shortest if it is admissible
+pub fn solve_with<H: Heuristic>(
+    puzzle: &Puzzle,
+    heuristic: &H,
+    budget: &Budget,
+) -> Result<Vec<Move>, SolveError> {
+    Solver::new(puzzle, heuristic)?.run(budget)
+}
+
+// how long can the solver search, cancelled by any of its clones
+#[derive(Clone, Default)]
+pub struct Budget {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl Budget {
+    // give up after some time
+    pub fn timed(duration: Duration) -> Self {
+        Self {
+            deadline: Some(Instant::now() + duration),
+            ..Default::default()
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    // the puzzle has no solution at all
+    Unsolvable,
+    // cancelled or out of time
+    Exhausted,
+    // more numbers than the search keeps, or lines longer than it checks
+    TooLarge,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Unsolvable => "the puzzle can not be solved",
+            Self::Exhausted => "the solver is cancelled or out of time",
+            Self::TooLarge => "the puzzle is too large for the solver",
+        })
+    }
+}
+
+impl Error for SolveError {}
+
+// IDA* without recursion, so that it can be paused and resumed on the
+// next frame where nothing else can run beside the game
+pub struct Solver<H: Heuristic> {
+    heuristic: H,
+    // the cell which slides into the blank on each cell by each move
+    sources: Vec<[Option<usize>; 4]>,
+    blank: usize,
+    tiles: Vec<u8>,
+    // the estimate of the board to be solved
+    root: H::Estimate,
+    bound: u32,
+    // the smallest estimate over the bound, to deepen it to next time
+    next: u32,
+    // boards on the path from the root, the moves between them
+    stack: Vec<Frame<H::Estimate>>,
+    path: Vec<Move>,
+}
+
+struct Frame<E> {
+    estimate: E,
+    // moves tried so far, in the order of all moves
+    tried: usize,
+}
+
+impl<H: Heuristic> Solver<H> {
+    // check the budget once every so many nodes
+    const CHECK: u64 = 4096;
+
+    // numbers are kept as u8
+    const MAX_CELLS: usize = u8::MAX as usize + 1;
+
+    pub fn new(puzzle: &Puzzle, heuristic: H) -> Result<Self, SolveError> {
+        if puzzle.tiles().len() > Self::MAX_CELLS {
+            return Err(SolveError::TooLarge);
+        }
+        if !puzzle.is_solvable() {
+            return Err(SolveError::Unsolvable);
+        }
+        let tiles = puzzle
+            .tiles()
+            .iter()
+            .map(|&tile| tile as u8)
+            .collect::<Vec<_>>();
+        let (width, height) = (puzzle.width(), puzzle.height());
+        let sources = (0..tiles.len())
+            .map(|blank| {
+                let (x, y) = (blank % width, blank / width);
+                Move::ALL.map(|mv| match mv {
+                    Move::Up => (y + 1 < height).then(|| blank + width),
+                    Move::Down => (y > 0).then(|| blank - width),
+                    Move::Left => (x + 1 < width).then(|| blank + 1),
+                    Move::Right => (x > 0).then(|| blank - 1),
+                })
+            })
+            .collect();
+        let (x, y) = puzzle.blank();
+        let root = heuristic.estimate(&tiles);
+        Ok(Self {
+            sources,
+            blank: y * width + x,
+            tiles,
+            root,
+            // the first bound is taken once started
+            next: heuristic.moves(root),
+            bound: 0,
+            stack: Vec::new(),
+            path: Vec::new(),
+            heuristic,
+        })
+    }
+
+    // search until solved or the budget is exhausted
+    pub fn run(&mut self, budget: &Budget) -> Result<Vec<Move>, SolveError> {
+        loop {
+            if let Some(moves) = self.step(Self::CHECK) {
+                return Ok(moves);
+            }
+            if budget.is_exhausted() {
+                return Err(SolveError::Exhausted);
+            }
+        }
+    }
+
+    // visit or leave at most so many nodes, the moves once solved
+    pub fn step(&mut self, nodes: u64) -> Option<Vec<Move>> {
+        for _ in 0..nodes {
+            let estimate = match self.stack.is_empty() {
+                // start over from the root with a deeper bound
+                true => {
+                    self.bound = mem::replace(&mut self.next, u32::MAX);
+                    self.root
+                }
+                false => match self.advance() {
+                    Some(estimate) => estimate,
+                    // every move from here is tried
+                    None => {
+                        self.stack.pop();
+                        self.back();
+                        continue;
+                    }
+                },
+            };
+            let depth = self.path.len() as u32;
+            let moves = self.heuristic.moves(estimate);
+            if depth + moves > self.bound {
+                self.next = self.next.min(depth + moves);
+                self.back();
+            } else if moves == 0 && self.is_solved() {
+                return Some(self.path.clone());
+            } else {
+                self.stack.push(Frame { estimate, tried: 0 });
+            }
+        }
+        None
+    }
+
+    // take the next move from the deepest board, the estimate after it
+    fn advance(&mut self) -> Option<H::Estimate> {
+        let last = self.path.last().copied();
+        let frame = self.stack.len() - 1;
+        while let Some(&mv) = Move::ALL.get(self.stack[frame].tried) {
+            self.stack[frame].tried += 1;
+            // never undo the last move
+            if last == Some(mv.opposite()) {
+                continue;
+            }
+            if let Some((from, to)) = self.slide(mv) {
+                self.path.push(mv);
+                let estimate = self.stack[frame].estimate;
+                return Some(self.heuristic.update(&self.tiles, estimate, from, to));
+            }
+        }
+        None
+    }
+
+    // undo the last move on the path
+    fn back(&mut self) {
+        if let Some(mv) = self.path.pop() {
+            self.slide(mv.opposite());
+        }
+    }
+
+    // slide a number into the blank, return the cells it moved from and to
+    fn slide(&mut self, mv: Move) -> Option<(usize, usize)> {
+        // moves are in the order of all moves
+        let source = self.sources[self.blank][mv as usize]?;
+        let target = self.blank;
+        self.tiles.swap(source, target);
+        self.blank = source;
+        Some((source, target))
+    }
+
+    fn is_solved(&self) -> bool {
+        let size = self.tiles.len();
+        self.tiles
+            .iter()
+            .enumerate()
+            .all(|(index, &tile)| (index + 1) % size == tile as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, VecDeque};
+
+    fn solves(puzzle: &Puzzle, moves: &[Move]) -> bool {
+        let mut puzzle = puzzle.clone();
+        moves.iter().all(|&mv| puzzle.apply(mv).is_ok()) && puzzle.is_solved()
+    }
+
+    // fewest moves of every solvable board by breadth first search
+    fn distances(width: usize, height: usize) -> HashMap<Vec<u8>, u32> {
+        let solved = Puzzle::new(width, height);
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::from([(solved, 0)]);
+        while let Some((puzzle, distance)) = queue.pop_front() {
+            let tiles = puzzle.tiles().iter().map(|&tile| tile as u8).collect();
+            if distances.contains_key(&tiles) {
+                continue;
+            }
+            distances.insert(tiles, distance);
+            puzzle.legal_moves().into_iter().for_each(|mv| {
+                let mut next = puzzle.clone();
+                next.apply(mv).unwrap();
+                queue.push_back((next, distance + 1));
+            });
+        }
+        distances
+    }
+
+    // from the tiles kept as u8 in the search
+    fn puzzle(width: usize, height: usize, tiles: &[u8]) -> Puzzle {
+        let tiles = tiles.iter().map(|&tile| tile as usize).collect();
+        Puzzle::from_tiles(width, height, tiles).unwrap()
+    }
+
+    #[test]
+    fn solutions_solve() {
+        [(2, 2), (3, 3), (2, 4), (4, 2), (3, 4), (5, 2)]
+            .into_iter()
+            .for_each(|(width, height)| {
+                (0..10).for_each(|seed| {
+                    let puzzle = Puzzle::shuffled(width, height, seed);
+                    let moves = solve(&puzzle, &Budget::default()).unwrap();
+                    assert!(solves(&puzzle, &moves), "{}", puzzle);
+                });
+            });
+        let solved = Puzzle::new(4, 4);
+        assert_eq!(solve(&solved, &Budget::default()), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn linear_conflict_is_admissible() {
+        let heuristic = LinearConflict::new(3, 3).unwrap();
+        let distances = distances(3, 3);
+        assert_eq!(distances.len(), 181440);
+        distances.iter().for_each(|(tiles, &distance)| {
+            assert!(heuristic.estimate(tiles) <= distance, "{:?}", tiles);
+        });
+    }
+
+    #[test]
+    fn solutions_are_shortest() {
+        let distances = distances(3, 3);
+        distances
+            .iter()
+            .step_by(997)
+            .for_each(|(tiles, &distance)| {
+                let puzzle = puzzle(3, 3, tiles);
+                let moves = solve(&puzzle, &Budget::default()).unwrap();
+                assert_eq!(moves.len() as u32, distance, "{}", puzzle);
+                assert!(solves(&puzzle, &moves));
+            });
+    }
+
+    #[test]
+    fn resumes_where_it_paused() {
+        (0..10).for_each(|seed| {
+            let puzzle = Puzzle::shuffled(3, 3, seed);
+            let heuristic = LinearConflict::new(3, 3).unwrap();
+            let mut solver = Solver::new(&puzzle, &heuristic).unwrap();
+            let moves = loop {
+                if let Some(moves) = solver.step(7) {
+                    break moves;
+                }
+            };
+            assert_eq!(Ok(moves), solve(&puzzle, &Budget::default()));
+        });
+    }
+
+    #[test]
+    fn cancelled() {
+        let budget = Budget::default();
+        budget.cancel();
+        let puzzle = Puzzle::shuffled(5, 5, 0);
+        assert_eq!(solve(&puzzle, &budget), Err(SolveError::Exhausted));
+        let budget = Budget::timed(Duration::ZERO);
+        assert_eq!(solve(&puzzle, &budget), Err(SolveError::Exhausted));
+    }
+
+    #[test]
+    fn too_large() {
+        let puzzle = Puzzle::new(20, 20);
+        assert!(matches!(
+            Solver::new(&puzzle, LinearConflict::new(20, 20).unwrap()),
+            Err(SolveError::TooLarge)
+        ));
+        assert!(matches!(
+            LinearConflict::new(33, 2),
+            Err(SolveError::TooLarge)
+        ));
+        assert_eq!(
+            solve(&Puzzle::new(40, 2), &Budget::default()),
+            Err(SolveError::TooLarge)
+        );
+    }
+}
@@ -0,0 +1,638 @@
+//! disjoint additive pattern databases, each group of numbers stores the
+//! fewest moves of its own numbers to put them in place wherever the
+//! others are, so the groups can be summed up as an admissible estimate.
+use super::Heuristic;
+use std::{collections::VecDeque, error::Error, fmt};
+
+pub struct PatternDatabase {
+    width: usize,
+    height: usize,
+    groups: Vec<Pattern>,
+    // the group of each number and its index in the group, if any
+    member_of: Vec<Option<(usize, usize)>>,
+    // where each cell goes when reflected along the diagonal
+    flip: Vec<u8>,
+}
+
+// the moves of a group are its manhattan distance plus twice the moves
+// going away, which are small enough to be packed in half a byte
+struct Pattern {
+    cells: usize,
+    tiles: Vec<u8>,
+    // extra moves of each placement of the numbers, two in a byte
+    table: Vec<u8>,
+    // distance of each number in order to its goal from each cell
+    distances: Vec<u8>,
+}
+
+impl PatternDatabase {
+    const MAGIC: &'static [u8; 4] = b"SPDB";
+    const VERSION: u8 = 1;
+    // at most so many groups, kept in each estimate
+    const GROUPS: usize = 5;
+
+    // breadth first search from the goal for each group, this takes a
+    // while and lots of memory for large groups, better done offline
+    pub fn generate(width: usize, height: usize, groups: &[Vec<u8>]) -> Self {
+        assert!(width * height <= Pattern::CELLS);
+        assert!(groups.len() <= Self::GROUPS);
+        assert!(groups.iter().all(|tiles| tiles.len() <= Pattern::MAX));
+        let groups = groups
+            .iter()
+            .map(|tiles| Pattern::search(width, height, tiles))
+            .collect::<Vec<_>>();
+        Self::with_groups(width, height, groups)
+    }
+
+    // split numbers into groups by sizes, taking the goal cells of the
+    // left column, the other columns below the top row and the top row
+    // last, so that each group is a block like the 6-6-3 of 4x4
+    pub fn partition(width: usize, height: usize, sizes: &[usize]) -> Vec<Vec<u8>> {
+        let below = (1..width).flat_map(|x| (1..height).map(move |y| y * width + x));
+        let mut tiles = (0..height)
+            .map(|y| y * width)
+            .chain(below)
+            .chain(1..width)
+            // the blank is not in any group
+            .filter(|&cell| cell + 1 < width * height)
+            .map(|cell| cell as u8 + 1);
+        sizes
+            .iter()
+            .map(|&size| tiles.by_ref().take(size).collect())
+            .collect()
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Self::MAGIC.to_vec();
+        bytes.extend([
+            Self::VERSION,
+            self.width as u8,
+            self.height as u8,
+            self.groups.len() as u8,
+        ]);
+        self.groups.iter().for_each(|group| {
+            bytes.push(group.tiles.len() as u8);
+            bytes.extend(&group.tiles);
+            bytes.extend(&group.table);
+        });
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PatternError> {
+        let mut reader = Reader(bytes);
+        if reader.take(4)? != Self::MAGIC {
+            return Err(PatternError::NotPatterns);
+        }
+        match reader.byte()? {
+            Self::VERSION => (),
+            version => return Err(PatternError::Version(version)),
+        }
+        let (width, height) = (reader.byte()? as usize, reader.byte()? as usize);
+        let cells = width * height;
+        if width < 2 || height < 2 || cells > Pattern::CELLS {
+            return Err(PatternError::Corrupted);
+        }
+        let count = reader.byte()?;
+        if count as usize > Self::GROUPS {
+            return Err(PatternError::Corrupted);
+        }
+        let groups = (0..count)
+            .map(|_| {
+                let len = reader.byte()?.into();
+                let tiles = reader.take(len)?.to_vec();
+                let valid = |&tile: &u8| tile > 0 && (tile as usize) < cells;
+                // checked before sizing the table, which needs distinct
+                // numbers fewer than the cells
+                let mut distinct = tiles.clone();
+                distinct.sort_unstable();
+                distinct.dedup();
+                if len > Pattern::MAX
+                    || len >= cells
+                    || distinct.len() != len
+                    || !tiles.iter().all(valid)
+                {
+                    return Err(PatternError::Corrupted);
+                }
+                let table = reader.take(Pattern::size(cells, len).div_ceil(2))?.to_vec();
+                Ok(Pattern::new(width, cells, tiles, table))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // each number belongs to one group at most
+        let mut tiles = groups
+            .iter()
+            .flat_map(|group| &group.tiles)
+            .collect::<Vec<_>>();
+        let len = tiles.len();
+        tiles.sort_unstable();
+        tiles.dedup();
+        if !reader.0.is_empty() || tiles.len() != len {
+            return Err(PatternError::Corrupted);
+        }
+        Ok(Self::with_groups(width, height, groups))
+    }
+
+    fn with_groups(width: usize, height: usize, groups: Vec<Pattern>) -> Self {
+        let cells = width * height;
+        let mut member_of = vec![None; cells];
+        groups.iter().enumerate().for_each(|(index, group)| {
+            group.tiles.iter().enumerate().for_each(|(member, &tile)| {
+                member_of[tile as usize] = Some((index, member));
+            });
+        });
+        let flip = (0..cells)
+            .map(|cell| (cell % width * width + cell / width) as u8)
+            .collect();
+        Self {
+            width,
+            height,
+            groups,
+            member_of,
+            flip,
+        }
+    }
+
+    // a reflected number is the one whose goal is the flipped goal
+    fn reflect(&self, tile: u8) -> u8 {
+        match tile {
+            0 => 0,
+            _ => self.flip[tile as usize - 1] + 1,
+        }
+    }
+
+    // cells and numbers of the board itself
+    fn direct<'a>(&'a self, tiles: &'a [u8]) -> impl Iterator<Item = (u8, u8)> + 'a {
+        tiles
+            .iter()
+            .enumerate()
+            .map(|(cell, &tile)| (cell as u8, tile))
+    }
+
+    // cells and numbers of the reflected board
+    fn reflected<'a>(&'a self, tiles: &'a [u8]) -> impl Iterator<Item = (u8, u8)> + 'a {
+        tiles
+            .iter()
+            .enumerate()
+            .map(|(cell, &tile)| (self.flip[cell], self.reflect(tile)))
+    }
+
+    // look up every group of a whole board
+    fn placements(&self, board: impl Iterator<Item = (u8, u8)>) -> Placements {
+        let mut placements = Placements {
+            cells: [[0; Pattern::MAX]; Self::GROUPS],
+            moves: [0; Self::GROUPS],
+            total: 0,
+        };
+        board.for_each(|(cell, tile)| {
+            if let Some((group, member)) = self.member_of[tile as usize] {
+                placements.cells[group][member] = cell;
+            }
+        });
+        self.groups.iter().enumerate().for_each(|(index, group)| {
+            placements.moves[index] = group.lookup(&placements.cells[index]) as u16;
+            placements.total += placements.moves[index] as u32;
+        });
+        placements
+    }
+
+    // only the group of moved number changed
+    fn update_group(&self, mut placements: Placements, tile: u8, to: u8) -> Placements {
+        if let Some((index, member)) = self.member_of[tile as usize] {
+            placements.cells[index][member] = to;
+            let moves = self.groups[index].lookup(&placements.cells[index]) as u16;
+            placements.total = placements.total + moves as u32 - placements.moves[index] as u32;
+            placements.moves[index] = moves;
+        }
+        placements
+    }
+}
+
+// cells of the numbers in each group and the moves of each, kept along
+// the search so that a move only looks up the group of moved number
+#[derive(Clone, Copy)]
+pub struct Placements {
+    cells: [[u8; Pattern::MAX]; PatternDatabase::GROUPS],
+    moves: [u16; PatternDatabase::GROUPS],
+    total: u32,
+}
+
+impl Heuristic for PatternDatabase {
+    // the board itself and its reflection along the diagonal if square,
+    // both need the same moves to solve
+    type Estimate = [Placements; 2];
+
+    fn estimate(&self, tiles: &[u8]) -> [Placements; 2] {
+        let direct = self.placements(self.direct(tiles));
+        [
+            direct,
+            match self.width == self.height {
+                true => self.placements(self.reflected(tiles)),
+                false => direct,
+            },
+        ]
+    }
+
+    fn update(
+        &self,
+        tiles: &[u8],
+        estimate: [Placements; 2],
+        _: usize,
+        to: usize,
+    ) -> [Placements; 2] {
+        let tile = tiles[to];
+        let direct = self.update_group(estimate[0], tile, to as u8);
+        [
+            direct,
+            match self.width == self.height {
+                true => self.update_group(estimate[1], self.reflect(tile), self.flip[to]),
+                false => direct,
+            },
+        ]
+    }
+
+    fn moves(&self, estimate: [Placements; 2]) -> u32 {
+        estimate[0].total.max(estimate[1].total)
+    }
+}
+
+impl Pattern {
+    // at most so many numbers in a group
+    const MAX: usize = 8;
+    // cells are taken as bits of u128
+    const CELLS: usize = 128;
+
+    fn new(width: usize, cells: usize, tiles: Vec<u8>, table: Vec<u8>) -> Self {
+        let distances = tiles
+            .iter()
+            .flat_map(|&tile| {
+                let goal = tile as usize - 1;
+                (0..cells).map(move |cell| {
+                    let x = (goal % width).abs_diff(cell % width);
+                    let y = (goal / width).abs_diff(cell / width);
+                    (x + y) as u8
+                })
+            })
+            .collect();
+        Self {
+            cells,
+            tiles,
+            table,
+            distances,
+        }
+    }
+
+    // how many ways to place k numbers on n cells
+    fn size(cells: usize, tiles: usize) -> usize {
+        (cells - tiles + 1..=cells).product()
+    }
+
+    // rank distinct cells like a number in mixed radix n, n - 1, ...
+    // where each digit counts the cells not taken by former ones
+    fn rank(cells: usize, placement: impl Iterator<Item = usize>) -> usize {
+        let mut taken = 0u128;
+        placement.enumerate().fold(0, |rank, (index, cell)| {
+            let before = (taken & ((1 << cell) - 1)).count_ones() as usize;
+            taken |= 1 << cell;
+            rank * (cells - index) + cell - before
+        })
+    }
+
+    // the reverse of rank, fill placement with the cells
+    fn unrank(cells: usize, mut rank: usize, placement: &mut [usize]) {
+        let len = placement.len();
+        // digits from the last one
+        (0..len).rev().for_each(|index| {
+            let radix = cells - index;
+            placement[index] = rank % radix;
+            rank /= radix;
+        });
+        // turn digits into cells, skipping cells already taken
+        let mut taken = 0u128;
+        placement.iter_mut().for_each(|cell| {
+            let mut digit = *cell;
+            let mut candidate = 0;
+            loop {
+                if taken & (1 << candidate) == 0 {
+                    if digit == 0 {
+                        break;
+                    }
+                    digit -= 1;
+                }
+                candidate += 1;
+            }
+            taken |= 1 << candidate;
+            *cell = candidate;
+        });
+    }
+
+    // manhattan distance of the numbers, given their cells
+    fn distance(&self, placement: impl Iterator<Item = usize>) -> u32 {
+        placement
+            .take(self.tiles.len())
+            .enumerate()
+            .map(|(member, cell)| self.distances[member * self.cells + cell] as u32)
+            .sum()
+    }
+
+    // cells of the numbers in order, extra ones are ignored
+    fn lookup(&self, placement: &[u8]) -> u32 {
+        let placement = placement[..self.tiles.len()]
+            .iter()
+            .map(|&cell| cell as usize);
+        let rank = Self::rank(self.cells, placement.clone());
+        let extra = self.table[rank / 2] >> (rank % 2 * 4) & 0xf;
+        self.distance(placement) + 2 * extra as u32
+    }
+
+    // 0-1 breadth first search on placements of the numbers plus the
+    // blank, only moves of the numbers count
+    fn search(width: usize, height: usize, tiles: &[u8]) -> Self {
+        let cells = width * height;
+        let len = tiles.len();
+        let mut distance = vec![u8::MAX; Self::size(cells, len + 1)];
+        let mut queue = VecDeque::new();
+        // numbers and the blank are all at their goal
+        let goal = tiles
+            .iter()
+            .map(|&tile| tile as usize - 1)
+            .chain([cells - 1]);
+        let start = Self::rank(cells, goal);
+        distance[start] = 0;
+        queue.push_back(start);
+        let mut placement = vec![0; len + 1];
+        let mut next = vec![0; len + 1];
+        while let Some(rank) = queue.pop_front() {
+            let moves = distance[rank];
+            Self::unrank(cells, rank, &mut placement);
+            let blank = placement[len];
+            let (x, y) = (blank % width, blank / width);
+            let neighbours = [
+                (x > 0).then(|| blank - 1),
+                (x + 1 < width).then(|| blank + 1),
+                (y > 0).then(|| blank - width),
+                (y + 1 < height).then(|| blank + width),
+            ];
+            neighbours.into_iter().flatten().for_each(|cell| {
+                next.copy_from_slice(&placement);
+                // a number of the group slides into the blank, or not
+                let cost = match next[..len].iter().position(|&other| other == cell) {
+                    Some(index) => {
+                        next[index] = blank;
+                        1
+                    }
+                    None => 0,
+                };
+                next[len] = cell;
+                let rank = Self::rank(cells, next.iter().copied());
+                if moves + cost < distance[rank] {
+                    distance[rank] = moves + cost;
+                    match cost {
+                        0 => queue.push_front(rank),
+                        _ => queue.push_back(rank),
+                    }
+                }
+            });
+        }
+        let table = vec![0; Self::size(cells, len).div_ceil(2)];
+        let mut pattern = Self::new(width, cells, tiles.to_vec(), table);
+        // the fewest moves wherever the blank is, the extra moves are
+        // clamped so that it is still a lower bound
+        distance
+            .chunks(cells - len)
+            .enumerate()
+            .for_each(|(rank, chunk)| {
+                let moves = *chunk.iter().min().unwrap() as u32;
+                Self::unrank(cells, rank, &mut placement[..len]);
+                let extra = (moves - pattern.distance(placement.iter().copied())) / 2;
+                pattern.table[rank / 2] |= (extra.min(0xf) as u8) << (rank % 2 * 4);
+            });
+        pattern
+    }
+}
+
+// read the bytes in order
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], PatternError> {
+        if self.0.len() < len {
+            return Err(PatternError::Corrupted);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, PatternError> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatternError {
+    // not begin with the magic bytes
+    NotPatterns,
+    // made by another version
+    Version(u8),
+    // truncated or invalid
+    Corrupted,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotPatterns => f.write_str("not a pattern database"),
+            Self::Version(version) => write!(f, "unsupported pattern database version {}", version),
+            Self::Corrupted => f.write_str("the pattern database is corrupted"),
+        }
+    }
+}
+
+impl Error for PatternError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        puzzle::Puzzle,
+        solver::{solve, solve_with, Budget},
+    };
+    use rand::{seq::SliceRandom, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::{fs, time::Duration};
+
+    fn tiles(puzzle: &Puzzle) -> Vec<u8> {
+        puzzle.tiles().iter().map(|&tile| tile as u8).collect()
+    }
+
+    #[test]
+    fn partition_in_blocks() {
+        let groups = PatternDatabase::partition(4, 4, &[6, 6, 3]);
+        assert_eq!(
+            groups,
+            [
+                vec![1, 5, 9, 13, 6, 10],
+                vec![14, 7, 11, 15, 8, 12],
+                vec![2, 3, 4]
+            ]
+        );
+        let groups = PatternDatabase::partition(3, 3, &[4, 4]);
+        assert_eq!(groups, [vec![1, 4, 7, 5], vec![8, 6, 2, 3]]);
+    }
+
+    #[test]
+    fn rank_round_trip() {
+        let cells = 12;
+        let mut placement = [0; 4];
+        (0..Pattern::size(cells, 4)).step_by(7).for_each(|rank| {
+            Pattern::unrank(cells, rank, &mut placement);
+            assert_eq!(Pattern::rank(cells, placement.into_iter()), rank);
+        });
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let groups = PatternDatabase::partition(3, 3, &[4, 4]);
+        let database = PatternDatabase::generate(3, 3, &groups);
+        let bytes = database.to_bytes();
+        let read = PatternDatabase::from_bytes(&bytes).unwrap();
+        assert_eq!(read.to_bytes(), bytes);
+        assert_eq!(
+            PatternDatabase::from_bytes(b"NOPE").err(),
+            Some(PatternError::NotPatterns)
+        );
+        let mut version = bytes.clone();
+        version[4] = 9;
+        assert_eq!(
+            PatternDatabase::from_bytes(&version).err(),
+            Some(PatternError::Version(9))
+        );
+        assert_eq!(
+            PatternDatabase::from_bytes(&bytes[..bytes.len() - 1]).err(),
+            Some(PatternError::Corrupted)
+        );
+    }
+
+    #[test]
+    fn corrupted_groups() {
+        let header = |width: u8, height: u8, group: &[u8]| {
+            let mut bytes = PatternDatabase::MAGIC.to_vec();
+            bytes.extend([PatternDatabase::VERSION, width, height, 1]);
+            bytes.push(group.len() as u8);
+            bytes.extend(group);
+            bytes
+        };
+        // more numbers than the cells, or the same number twice
+        [
+            header(2, 2, &[1, 2, 3, 1, 2]),
+            header(2, 2, &[1, 2, 3, 1, 2, 3, 1, 2]),
+            header(2, 2, &[1, 2, 3, 3]),
+            header(3, 3, &[1, 1, 2]),
+            header(3, 3, &[0, 1, 2]),
+            header(3, 3, &[1, 2, 9]),
+        ]
+        .iter()
+        .for_each(|bytes| {
+            assert_eq!(
+                PatternDatabase::from_bytes(bytes).err(),
+                Some(PatternError::Corrupted)
+            );
+        });
+    }
+
+    // both are admissible, so both find the shortest solutions
+    #[test]
+    fn same_lengths_as_linear_conflict() {
+        [
+            (3, 3, vec![4, 4]),
+            (4, 3, vec![4, 4, 3]),
+            (3, 4, vec![4, 4, 3]),
+        ]
+        .into_iter()
+        .for_each(|(width, height, sizes)| {
+            let groups = PatternDatabase::partition(width, height, &sizes);
+            let database = PatternDatabase::generate(width, height, &groups);
+            (0..30).for_each(|seed| {
+                let puzzle = Puzzle::shuffled(width, height, seed);
+                let budget = Budget::default();
+                let patterns = solve_with(&puzzle, &database, &budget).unwrap();
+                let linear = solve(&puzzle, &budget).unwrap();
+                assert_eq!(patterns.len(), linear.len(), "{}", puzzle);
+                let estimate = database.estimate(&tiles(&puzzle));
+                assert!(database.moves(estimate) <= linear.len() as u32);
+            });
+        });
+    }
+
+    // the first four of Korf's 100 boards, turned around for the blank at
+    // the last cell, solved within the budget of the game
+    #[test]
+    fn known_boards() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/patterns/4x4.pdb");
+        let database = PatternDatabase::from_bytes(&fs::read(path).unwrap()).unwrap();
+        [
+            ("4x4:13,6,8,12/15,14,0,10/11,7,4,5/9,1,3,2", 57),
+            ("4x4:10,5,1,0/15,9,13,14/2,8,4,7/6,12,11,3", 55),
+            ("4x4:1,15,10,13/0,11,4,7/12,6,5,3/14,8,9,2", 59),
+            ("4x4:10,7,12,13/3,15,14,8/0,2,5,1/9,6,4,11", 56),
+        ]
+        .into_iter()
+        .for_each(|(notation, length)| {
+            let puzzle = notation.parse::<Puzzle>().unwrap();
+            let budget = Budget::timed(Duration::from_secs(10));
+            let moves = solve_with(&puzzle, &database, &budget).unwrap();
+            assert_eq!(moves.len(), length, "{}", notation);
+            let mut solved = puzzle.clone();
+            moves.into_iter().for_each(|mv| {
+                solved.apply(mv).unwrap();
+            });
+            assert!(solved.is_solved());
+        });
+    }
+
+    // boards some random moves away from the goal, the same lengths as
+    // without the shipped database
+    #[test]
+    fn shipped_5x5() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/patterns/5x5.pdb");
+        let database = PatternDatabase::from_bytes(&fs::read(path).unwrap()).unwrap();
+        (0..5).for_each(|seed| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let mut puzzle = Puzzle::new(5, 5);
+            (0..60).for_each(|_| {
+                let mv = *puzzle.legal_moves().choose(&mut rng).unwrap();
+                puzzle.apply(mv).unwrap();
+            });
+            let budget = Budget::default();
+            let patterns = solve_with(&puzzle, &database, &budget).unwrap();
+            assert_eq!(patterns.len(), solve(&puzzle, &budget).unwrap().len());
+        });
+    }
+
+    #[test]
+    fn reflected_estimate_is_updated() {
+        let groups = PatternDatabase::partition(3, 3, &[4, 4]);
+        let database = PatternDatabase::generate(3, 3, &groups);
+        let mut puzzle = Puzzle::shuffled(3, 3, 1);
+        let mut estimate = database.estimate(&tiles(&puzzle));
+        (0..100).for_each(|step| {
+            let moves = puzzle.legal_moves();
+            let mv = moves[step % moves.len()];
+            let (x, y) = puzzle.blank();
+            puzzle.apply(mv).unwrap();
+            let (bx, by) = puzzle.blank();
+            let tiles = tiles(&puzzle);
+            estimate = database.update(&tiles, estimate, by * 3 + bx, y * 3 + x);
+            let whole = database.estimate(&tiles);
+            assert_eq!(database.moves(estimate), database.moves(whole));
+            assert_eq!(estimate[1].total, whole[1].total);
+        });
+    }
+}
@@ -0,0 +1,91 @@
+//! keep small files between sessions, in the config directory on native,
+//! or in the local storage of browser on wasm.
+use bevy::log::warn;
+use serde::{de::DeserializeOwned, Serialize};
+
+// read a value, none if it is missing or broken
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let text = read(key)?;
+    ron::from_str(&text)
+        .map_err(|err| warn!("ignore broken {}: {}", key, err))
+        .ok()
+}
+
+// write a value, only warn if failed since nothing else can be done
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    match ron::ser::to_string_pretty(value, Default::default()) {
+        Ok(text) => {
+            if let Err(err) = write(key, &text) {
+                warn!("can not save {}: {}", key, err);
+            }
+        }
+        Err(err) => warn!("can not save {}: {}", key, err),
+    }
+}
+
+// forget a value, only warn if failed as saving does
+pub fn remove(key: &str) {
+    if let Err(err) = delete(key) {
+        warn!("can not remove {}: {}", key, err);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path(key: &str) -> Option<std::path::PathBuf> {
+    Some(dirs::config_dir()?.join(env!("CARGO_PKG_NAME")).join(key))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read(key: &str) -> Option<String> {
+    std::fs::read_to_string(path(key)?).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(key: &str, text: &str) -> Result<(), String> {
+    let path = path(key).ok_or("no config directory")?;
+    let write = || {
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(&path, text)
+    };
+    write().map_err(|err| err.to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn delete(key: &str) -> Result<(), String> {
+    match path(key).map(std::fs::remove_file) {
+        Some(Err(err)) if err.kind() != std::io::ErrorKind::NotFound => Err(err.to_string()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read(key: &str) -> Option<String> {
+    storage()?.get_item(&storage_key(key)).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write(key: &str, text: &str) -> Result<(), String> {
+    storage()
+        .ok_or("no local storage")?
+        .set_item(&storage_key(key), text)
+        .map_err(|err| format!("{:?}", err))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn delete(key: &str) -> Result<(), String> {
+    storage()
+        .ok_or("no local storage")?
+        .remove_item(&storage_key(key))
+        .map_err(|err| format!("{:?}", err))
+}
+
+// keys of local storage are shared by the whole site
+#[cfg(target_arch = "wasm32")]
+fn storage_key(key: &str) -> String {
+    format!("{}/{}", env!("CARGO_PKG_NAME"), key)
+}
@@ -0,0 +1,269 @@
+//! the look of the game, colors, fonts and the slider texture, read from
+//! theme files in `assets/themes` and put on everything on screen again
+//! once another one is picked in the settings screen.
+use bevy::{
+    asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub struct Themes;
+
+impl Themes {
+    fn setup(mut commands: Commands, server: Res<AssetServer>) {
+        let handles = ThemeName::ALL
+            .iter()
+            .map(|name| server.load(name.path()))
+            .collect();
+        commands.insert_resource(ThemeFiles(handles));
+        // until the file of picked one is loaded
+        commands.insert_resource(Theme::new(&ThemeFile::default(), &server));
+    }
+
+    // use the picked theme once its file is loaded, or changed on disk
+    fn switch(
+        mut events: EventReader<AssetEvent<ThemeFile>>,
+        name: Res<ThemeName>,
+        files: Res<ThemeFiles>,
+        assets: Res<Assets<ThemeFile>>,
+        server: Res<AssetServer>,
+        mut theme: ResMut<Theme>,
+    ) {
+        let handle = &files.0[*name as usize];
+        let loaded = events.iter().any(|event| match event {
+            AssetEvent::Created { handle: loaded } | AssetEvent::Modified { handle: loaded } => {
+                loaded == handle
+            }
+            AssetEvent::Removed { .. } => false,
+        });
+        if name.is_changed() || loaded {
+            if let Some(file) = assets.get(handle) {
+                *theme = Theme::new(file, &server);
+            }
+        }
+    }
+
+    // put the theme on everything on screen once it is changed
+    #[allow(clippy::type_complexity)]
+    fn restyle(
+        theme: Res<Theme>,
+        mut clear: ResMut<ClearColor>,
+        mut query: Query<(
+            &Themed,
+            Option<&Interaction>,
+            Option<&mut UiColor>,
+            Option<&mut Text>,
+            Option<&mut Handle<Image>>,
+        )>,
+    ) {
+        if !theme.is_changed() {
+            return;
+        }
+        clear.0 = theme.palette.background;
+        query.for_each_mut(|(&themed, interaction, color, text, image)| match themed {
+            Themed::Button | Themed::Popup => {
+                if let Some(mut color) = color {
+                    *color = match interaction {
+                        Some(Interaction::Hovered) => theme.palette.hovered,
+                        _ => theme.color(themed),
+                    }
+                    .into();
+                }
+            }
+            Themed::Slider => {
+                if let Some(mut image) = image {
+                    *image = theme.slider.clone();
+                }
+            }
+            _ => {
+                if let Some(mut text) = text {
+                    let style = theme.style(themed);
+                    text.sections.iter_mut().for_each(|section| {
+                        section.style.color = style.color;
+                        section.style.font = style.font.clone();
+                    });
+                }
+            }
+        });
+    }
+}
+
+impl Plugin for Themes {
+    fn build(&self, app: &mut App) {
+        #[derive(SystemLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct ThemeSwitched;
+        app.add_asset::<ThemeFile>()
+            .init_asset_loader::<ThemeLoader>()
+            .init_resource::<ThemeName>()
+            .insert_resource(ClearColor(ThemeFile::default().palette.background))
+            // before any screen is built with it
+            .add_startup_system_to_stage(StartupStage::PreStartup, Self::setup)
+            .add_system_to_stage(CoreStage::PreUpdate, Self::switch.label(ThemeSwitched))
+            .add_system_to_stage(CoreStage::PreUpdate, Self::restyle.after(ThemeSwitched));
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeName {
+    #[default]
+    Classic,
+    Dark,
+    HighContrast,
+}
+
+impl ThemeName {
+    pub const ALL: [Self; 3] = [Self::Classic, Self::Dark, Self::HighContrast];
+
+    pub fn next(self) -> Self {
+        crate::cycle(&Self::ALL, self)
+    }
+
+    fn path(self) -> &'static str {
+        match self {
+            Self::Classic => "themes/classic.theme.ron",
+            Self::Dark => "themes/dark.theme.ron",
+            Self::HighContrast => "themes/high_contrast.theme.ron",
+        }
+    }
+}
+
+impl fmt::Display for ThemeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Classic => "classic",
+            Self::Dark => "dark",
+            Self::HighContrast => "high contrast",
+        })
+    }
+}
+
+// colors of each part of screen
+#[derive(Clone, Copy, Deserialize)]
+pub struct Palette {
+    pub background: Color,
+    pub button: Color,
+    pub hovered: Color,
+    pub button_text: Color,
+    // titles and values on the background
+    pub text: Color,
+    pub number: Color,
+    // the slider to move next
+    pub hint: Color,
+    pub popup: Color,
+}
+
+// a theme as written in assets, with paths instead of handles
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "9b3e6d2a-4f1c-4e8b-a7d5-3c2f8e1b6a90"]
+pub struct ThemeFile {
+    palette: Palette,
+    font: String,
+    number_font: String,
+    slider: String,
+}
+
+impl Default for ThemeFile {
+    // the same as `classic.theme.ron`
+    fn default() -> Self {
+        Self {
+            palette: Palette {
+                background: Color::ORANGE,
+                button: Color::YELLOW,
+                hovered: Color::GOLD,
+                button_text: Color::OLIVE,
+                text: Color::NAVY,
+                number: Color::YELLOW,
+                hint: Color::GOLD,
+                popup: Color::rgba(1.0, 1.0, 1.0, 0.9),
+            },
+            font: "fonts/VictorMono-BoldItalic.ttf".to_string(),
+            number_font: "fonts/VictorMono-Bold.ttf".to_string(),
+            slider: "images/slider_default.png".to_string(),
+        }
+    }
+}
+
+// handles of all shipped themes, in the order of names
+struct ThemeFiles(Vec<Handle<ThemeFile>>);
+
+// the theme in use
+pub struct Theme {
+    pub palette: Palette,
+    pub font: Handle<Font>,
+    pub number_font: Handle<Font>,
+    pub slider: Handle<Image>,
+}
+
+impl Theme {
+    fn new(file: &ThemeFile, server: &AssetServer) -> Self {
+        Self {
+            palette: file.palette,
+            font: server.load(file.font.as_str()),
+            number_font: server.load(file.number_font.as_str()),
+            slider: server.load(file.slider.as_str()),
+        }
+    }
+
+    pub fn color(&self, themed: Themed) -> Color {
+        match themed {
+            Themed::Button => self.palette.button,
+            Themed::ButtonText => self.palette.button_text,
+            // on the blue images of floating buttons
+            Themed::Icon => Color::BLACK,
+            Themed::Text => self.palette.text,
+            Themed::Number => self.palette.number,
+            Themed::Popup => self.palette.popup,
+            Themed::Slider => Color::WHITE,
+        }
+    }
+
+    pub fn style(&self, themed: Themed) -> TextStyle {
+        TextStyle {
+            color: self.color(themed),
+            font: match themed {
+                Themed::Number => self.number_font.clone(),
+                _ => self.font.clone(),
+            },
+            ..Default::default()
+        }
+    }
+
+    pub fn text(&self, value: &str, themed: Themed) -> Text {
+        Text::with_section(value, self.style(themed), Default::default())
+    }
+}
+
+// which part of theme an entity is drawn with
+#[derive(Component, Clone, Copy)]
+pub enum Themed {
+    Button,
+    ButtonText,
+    Icon,
+    Text,
+    Number,
+    Popup,
+    Slider,
+}
+
+#[derive(Default)]
+struct ThemeLoader;
+
+impl AssetLoader for ThemeLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let file: ThemeFile = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(file));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
@@ -0,0 +1,105 @@
+//! rows, buttons and texts shared by the screens, each button has the
+//! label of its screen on it and on its text.
+use crate::{
+    theme::{Theme, Themed},
+    TextLabel, TextScale,
+};
+use bevy::prelude::*;
+
+// text of buttons in rows of a few, and of buttons on their own
+pub const SMALL: TextScale = TextScale {
+    width: 0.025,
+    height: 0.05,
+};
+pub const LARGE: TextScale = TextScale {
+    width: 0.05,
+    height: 0.1,
+};
+
+// things spread out in a line, sized in percent of the parent
+pub fn row(
+    parent: &mut ChildBuilder,
+    width: f32,
+    height: f32,
+    children: impl FnOnce(&mut ChildBuilder),
+) {
+    parent
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::SpaceBetween,
+                margin: Rect::all(Val::Auto),
+                size: Size::new(Val::Percent(width), Val::Percent(height)),
+                ..Default::default()
+            },
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .with_children(children);
+}
+
+// a button as high as its row, the width in percent of it
+pub fn button<L: Component + Copy>(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    label: L,
+    text: &str,
+    width: f32,
+    scale: TextScale,
+) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                size: Size::new(Val::Percent(width), Val::Percent(100.0)),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(label)
+        .insert(Themed::Button)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: theme.text(text, Themed::ButtonText),
+                    ..Default::default()
+                })
+                .insert(TextLabel::with_section(scale))
+                .insert(Themed::ButtonText)
+                .insert(label);
+        });
+}
+
+// a line of text, marked to be found again for updates
+pub fn text<L: Component>(
+    parent: &mut ChildBuilder,
+    theme: &Theme,
+    label: L,
+    text: &str,
+    scale: TextScale,
+) {
+    parent
+        .spawn_bundle(TextBundle {
+            text: theme.text(text, Themed::Text),
+            ..Default::default()
+        })
+        .insert(label)
+        .insert(TextLabel::with_section(scale))
+        .insert(Themed::Text);
+}
+
+// a line of text on its own, in the middle of the space around it
+pub fn heading(parent: &mut ChildBuilder, theme: &Theme, text: &str, scale: TextScale) {
+    parent
+        .spawn_bundle(TextBundle {
+            style: Style {
+                margin: Rect::all(Val::Auto),
+                ..Default::default()
+            },
+            text: theme.text(text, Themed::Text),
+            ..Default::default()
+        })
+        .insert(TextLabel::with_section(scale))
+        .insert(Themed::Text);
+}